@@ -10,9 +10,16 @@ mod trabajo_final_reporte {
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
     use trabajo_final::ClubRef;
-    use trabajo_final::trabajo_final::{Club, Socio, Actividad, Categoria, Pago};
+    use trabajo_final::trabajo_final::{Club, ClubError, Socio, Categoria, IdCategoria, IdActividad, Pago, Rol, Recargo, CicloFacturacion, Cupon, Descuento, Indexacion, Redondeo, EstadoDeCuenta, EstadoSocio, FiltroSocios, FiltroPagos, SocioAImportar};
     use trabajo_final::fecha::Fecha;
 
+    /// Cantidad de socios o pagos que se le piden al club en cada llamada, para no pasarse de los límites de gas y tamaño.
+    #[cfg(not(test))]
+    const TAMAÑO_PAGINA: u64 = 100;
+    // En los tests se usan páginas chicas para que los reportes recorran varias
+    #[cfg(test)]
+    const TAMAÑO_PAGINA: u64 = 2;
+
     /// Genera un reporte sobre un Club
     #[ink(storage)]
     pub struct TrabajoFinalReporte {
@@ -23,6 +30,8 @@ mod trabajo_final_reporte {
     }
     impl TrabajoFinalReporte {
         /// Crea un nuevo contrato de Reporte, que apunta al Club dado
+        /// 
+        /// Si el club usa la política cerrada, tiene que otorgarle el rol Auditor a este contrato.
         #[cfg(not(test))]
         #[ink(constructor)]
         pub fn new(club: ClubRef) -> Self {
@@ -58,78 +67,172 @@ mod trabajo_final_reporte {
         }
 
         /// Devuelve un [Vec] con todos los socios morosos del club.
+        /// Sólo se tienen en cuenta los socios activos: los suspendidos y los dados de baja no son morosos.
+        /// Los dependientes de un grupo familiar son morosos cuando su titular lo es.
         #[ink(message)]
-        pub fn obtener_socios_morosos(&self) -> Vec<Socio> {
-            // socios guardados por id
-            let mut socios_morosos: Vec<u64> = Vec::new();
-            let pagos = self.club.get_pagos(None);
-            let fecha_actual = self.club.obtener_fecha_actual();
-            for pago in pagos {
-                if pago.es_moroso(fecha_actual) {
-                    socios_morosos.push(pago.get_socio());
+        pub fn obtener_socios_morosos(&self) -> Result<Vec<Socio>, ClubError> {
+            let socios_morosos = self.ids_socios_morosos()?;
+            let mut socios = Vec::new();
+            for &id in socios_morosos.iter() {
+                let socio = self.obtener_socio(id)?;
+                if socio.es_activo() {
+                    // Los pagos de un grupo familiar son del titular
+                    for &id_dependiente in socio.get_dependientes() {
+                        let dependiente = self.obtener_socio(id_dependiente)?;
+                        if dependiente.es_activo() {
+                            socios.push(dependiente);
+                        }
+                    }
+                    socios.push(socio);
                 }
             }
+            Ok(socios)
+        }
+
+        /// Obtiene el socio con la id dada, o [ClubError::SocioInexistente] si el club no lo tiene.
+        fn obtener_socio(&self, id: u64) -> Result<Socio, ClubError> {
+            self.club.get_socio(id)
+        }
+
+        /// Obtiene los ids de los socios con algún pago moroso, ordenados y sin repetir.
+        fn ids_socios_morosos(&self) -> Result<Vec<u64>, ClubError> {
+            // Un pago es moroso si no se completó ni se canceló y venció antes de hoy
+            let mut ayer = self.club.obtener_fecha_actual();
+            ayer.restar_dias(1);
+            let filtro = FiltroPagos { pagado: Some(false), vencimiento_hasta: Some(ayer), ..Default::default() };
+            let mut socios_morosos = Vec::new();
+            self.recorrer_pagos(filtro, |pago| {
+                socios_morosos.push(pago.get_socio());
+                Ok(())
+            })?;
             // Quitar los socios que se agregaron varias veces
             socios_morosos.sort();
             socios_morosos.dedup();
+            Ok(socios_morosos)
+        }
+
+        /// Recorre los socios del club que cumplen el filtro dado, pidiéndolos de a páginas.
+        fn recorrer_socios(&self, filtro: FiltroSocios, mut procesar: impl FnMut(Socio) -> Result<(), ClubError>) -> Result<(), ClubError> {
+            let mut desde = Some(0);
+            while let Some(id) = desde {
+                let (socios, siguiente) = self.club.get_socios_pagina(id, TAMAÑO_PAGINA, filtro.clone())?;
+                for socio in socios {
+                    procesar(socio)?;
+                }
+                desde = siguiente;
+            }
+            Ok(())
+        }
 
-            socios_morosos.iter().map(|&id| self.club.get_socio(id).unwrap()).collect()
+        /// Recorre los pagos del club que cumplen el filtro dado, pidiéndolos de a páginas.
+        fn recorrer_pagos(&self, filtro: FiltroPagos, mut procesar: impl FnMut(Pago) -> Result<(), ClubError>) -> Result<(), ClubError> {
+            let mut desde = Some(0);
+            while let Some(id) = desde {
+                let (pagos, siguiente) = self.club.get_pagos_pagina(id, TAMAÑO_PAGINA, filtro.clone())?;
+                for pago in pagos {
+                    procesar(pago)?;
+                }
+                desde = siguiente;
+            }
+            Ok(())
         }
 
         /// Devuelve la recaudación total de pagos realizados durante el mes pedido, para cada categoría, así también como el total.
-        /// el formato es [Categoria A, Categoria B, Categoria C, Total]
+        /// el formato es un [Vec] con la recaudación de cada categoría, en el orden de sus ids, y el total al final.
+        /// Cada pago se cuenta en la categoría en la que se cobró, aunque el socio haya cambiado de categoría después.
+        /// Los pagos parciales se cuentan en el mes en el que se hizo cada uno, aunque el pago no esté completo.
+        /// Los recargos por pagos atrasados se suman a la recaudación.
+        /// Los pagos revertidos se descuentan en el mes en el que se revirtieron, así que la recaudación de un mes puede ser negativa.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el mes no está entre 1 y 12.
         #[ink(message)]
-        pub fn informe_recaudacion(&self, año: i32, mes: i8) -> [u128; 4] {
-            assert!(mes >= 1 && mes <= 12, "Mes inválido");
-            let pagos = self.club.get_pagos(None);
-            let mut cantidades = [0; 4];
-            for pago in pagos {
-                if let Some(fecha_pagado) = pago.get_pagado() {
-                    if fecha_pagado.get_año() == año && fecha_pagado.get_mes() == mes {
-                        let i = self.club.get_socio(pago.get_socio()).unwrap().get_categoria().num();
-                        cantidades[i] += pago.get_monto();
+        pub fn informe_recaudacion(&self, año: i32, mes: i8) -> Result<Vec<i128>, ClubError> {
+            if !(1..=12).contains(&mes) {
+                return Err(ClubError::ValorInvalido);
+            }
+            let cantidad_categorias = self.club.get_categorias().len();
+            let mut cantidades = ink::prelude::vec![0; cantidad_categorias + 1];
+            self.recorrer_pagos(FiltroPagos::default(), |pago| {
+                let i = pago.get_categoria() as usize;
+                // Los pagos hechos en partes se cuentan en la fecha de cada parte
+                let mut cobros = pago.get_pagos_parciales().to_vec();
+                if cobros.is_empty() {
+                    if let Some(fecha_pagado) = pago.get_pagado() {
+                        cobros.push((fecha_pagado, pago.get_monto_pagado()));
+                    }
+                }
+                for (fecha, monto) in cobros {
+                    if fecha.get_año() == año && fecha.get_mes() == mes {
+                        cantidades[i] += i128::try_from(monto).map_err(|_| ClubError::Desbordamiento)?;
+                    }
+                }
+                if let Some((fecha, _)) = pago.get_revertido() {
+                    if fecha.get_año() == año && fecha.get_mes() == mes {
+                        cantidades[i] -= i128::try_from(pago.get_monto_pagado()).map_err(|_| ClubError::Desbordamiento)?;
                     }
                 }
+                Ok(())
+            })?;
+            cantidades[cantidad_categorias] = cantidades[..cantidad_categorias].iter().sum();
+            Ok(cantidades)
+        }
+
+        /// Devuelve un [Vec] con todos los socios activos no morosos que tienen permitido acceder a la actividad dada,
+        /// según las actividades de su categoría. Los dependientes de un grupo familiar no son morosos si su titular no lo es.
+        #[ink(message)]
+        pub fn socios_no_morosos_en_actividad(&self, actividad: IdActividad) -> Result<Vec<Socio>, ClubError> {
+            let socios_morosos = self.ids_socios_morosos()?;
+            // Los socios sólo se pueden comparar por dni
+            let mut dnis_morosos = Vec::new();
+            for &id in socios_morosos.iter() {
+                dnis_morosos.push(self.obtener_socio(id)?.get_dni());
             }
-            cantidades[3] = cantidades[0] + cantidades[1] + cantidades[2];
-            cantidades
+
+            let filtro = FiltroSocios { actividad: Some(actividad), estado: Some(EstadoSocio::Activo), ..Default::default() };
+            let mut socios = Vec::new();
+            self.recorrer_socios(filtro, |socio| {
+                let titular_moroso = socio.get_titular().is_some_and(|titular| socios_morosos.binary_search(&titular).is_ok());
+                if !dnis_morosos.contains(&socio.get_dni()) && !titular_moroso {
+                    socios.push(socio);
+                }
+                Ok(())
+            })?;
+            Ok(socios)
         }
 
-        /// Devuelve un [Vec] con todos los socios no morosos que tienen permitido acceder a la [Actividad] dada.
+        /// Devuelve un [Vec] con todos los socios que tienen saldo a favor (ver [Socio::get_saldo_a_favor]).
         #[ink(message)]
-        pub fn socios_no_morosos_en_actividad(&self, actividad: Actividad) -> Vec<Socio> {
-            // socios guardados por id
-            let mut socios_morosos: Vec<u64> = Vec::new();
-            let mut socios_no_morosos: Vec<u64> = Vec::new();
-            let pagos = self.club.get_pagos(None);
-            let fecha_actual = self.club.obtener_fecha_actual();
-            for pago in pagos {
-                let id_socio = pago.get_socio();
-                if pago.es_moroso(fecha_actual) {
-                    socios_morosos.push(id_socio);
-                } else {
-                    socios_no_morosos.push(id_socio);
+        pub fn socios_con_saldo_a_favor(&self) -> Result<Vec<Socio>, ClubError> {
+            let mut socios = Vec::new();
+            self.recorrer_socios(FiltroSocios::default(), |socio| {
+                if socio.get_saldo_a_favor() > 0 {
+                    socios.push(socio);
                 }
-            }
-            // Quitar los socios que se agregaron varias veces
-            socios_morosos.sort();
-            socios_morosos.dedup();
-            socios_no_morosos.sort();
-            socios_no_morosos.dedup();
-            
-            socios_no_morosos.iter()
-                // Quitar los socios no morosos que están en el vector de socios morosos
-                .filter(|id| socios_morosos.binary_search(id).is_err())
-                // Convertir a Socios
-                .map(|&id| self.club.get_socio(id).unwrap())
-                // Filtrar los que no pueden acceder a la actividad
-                .filter(|s| s.get_categoria().puede_acceder_a(actividad))
-                .collect()
+                Ok(())
+            })?;
+            Ok(socios)
+        }
+
+        /// Devuelve el estado de cuenta (ver [Club::estado_de_cuenta]) de cada socio que adeuda algo, de mayor a menor deuda.
+        /// Cada grupo familiar aparece una sola vez, con el dni del titular.
+        #[ink(message)]
+        pub fn informe_deudas(&self) -> Result<Vec<EstadoDeCuenta>, ClubError> {
+            let mut estados = Vec::new();
+            self.recorrer_socios(FiltroSocios::default(), |socio| {
+                if socio.get_titular().is_none() {
+                    let estado = self.club.estado_de_cuenta(socio.get_dni())?;
+                    if estado.get_deuda_total() > 0 {
+                        estados.push(estado);
+                    }
+                }
+                Ok(())
+            })?;
+            estados.sort_by_key(|estado| core::cmp::Reverse(estado.get_deuda_total()));
+            Ok(estados)
         }
     }
 
     // Todo esto es necesario para los tests :(
-    #[derive(scale::Encode, Clone)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
@@ -141,6 +244,10 @@ mod trabajo_final_reporte {
     impl core::fmt::Debug for ClubFalso {
         fn fmt(&self, _: &mut core::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> { Ok(()) }
     }
+    // El Club guarda todo en Mappings, que no se pueden codificar
+    impl scale::Encode for ClubFalso {
+        fn encode_to<T: scale::Output + ?Sized>(&self, _: &mut T) {}
+    }
     impl scale::Decode for ClubFalso {
         fn decode<I>(_: &mut I) -> Result<Self, scale::Error> where I: scale::Input { todo!() }
     }
@@ -159,26 +266,92 @@ mod trabajo_final_reporte {
             self.fecha_actual.sumar_dias(dias);
         }
         pub fn get_nombre(&self) -> String {self.club.get_nombre()}
-        pub fn set_politica_autorizacion(&mut self, usar_la_politica: bool) {self.club.set_politica_autorizacion(usar_la_politica);}
+        pub fn set_politica_autorizacion(&mut self, usar_la_politica: bool) -> Result<(), ClubError> {self.club.set_politica_autorizacion(usar_la_politica)}
         pub fn soy_el_dueño(&self) -> bool {self.club.soy_el_dueño()}
-        pub fn estoy_autorizado(&self) -> bool {self.club.estoy_autorizado()}
-        pub fn cambiar_dueño(&mut self, nuevo_dueño: AccountId) {self.club.cambiar_dueño(nuevo_dueño);}
+        pub fn tengo_rol(&self, rol: Rol) -> bool {self.club.tengo_rol(rol)}
+        pub fn proponer_dueño(&mut self, nuevo_dueño: AccountId) -> Result<(), ClubError> {self.club.proponer_dueño(nuevo_dueño)}
+        pub fn aceptar_dueño(&mut self) -> Result<(), ClubError> {self.club.aceptar_dueño()}
+        pub fn cancelar_propuesta(&mut self) -> Result<(), ClubError> {self.club.cancelar_propuesta()}
+        pub fn get_dueño_propuesto(&self) -> Option<AccountId> {self.club.get_dueño_propuesto()}
         pub fn get_dueño(&self) -> AccountId {self.club.get_dueño()}
-        pub fn agregar_autorizado(&mut self, quien: AccountId) {self.club.agregar_autorizado(quien);}
-        pub fn quitar_autorizado(&mut self, quien: AccountId) {self.club.quitar_autorizado(quien);}
-        pub fn get_autorizados(&self) -> Vec<AccountId> {self.club.get_autorizados()}
-        pub fn cambiar_nombre(&mut self, nuevo_nombre: String) {self.club.cambiar_nombre(nuevo_nombre);}
-        pub fn set_precio(&mut self, categoria: Categoria, nuevo_valor: u128) {self.club.set_precio(categoria, nuevo_valor);}
-        pub fn get_precio(&self, categoria: Categoria) -> u128 {self.club.get_precio(categoria)}
-        pub fn set_cantidad_pagos_bonificacion(&mut self, nuevo_valor:u16) {self.club.set_cantidad_pagos_bonificacion(nuevo_valor);}
+        pub fn otorgar_rol(&mut self, quien: AccountId, rol: Rol) -> Result<(), ClubError> {self.club.otorgar_rol(quien, rol)}
+        pub fn revocar_rol(&mut self, quien: AccountId, rol: Rol) -> Result<(), ClubError> {self.club.revocar_rol(quien, rol)}
+        pub fn get_roles(&self, cuenta: AccountId) -> Vec<Rol> {self.club.get_roles(cuenta)}
+        pub fn get_cuentas_con_rol(&self, rol: Rol) -> Vec<AccountId> {self.club.get_cuentas_con_rol(rol)}
+        pub fn cambiar_nombre(&mut self, nuevo_nombre: String) -> Result<(), ClubError> {self.club.cambiar_nombre(nuevo_nombre)}
+        pub fn set_precio(&mut self, categoria: IdCategoria, nuevo_valor: u128) -> Result<(), ClubError> {self.club.set_precio(categoria, nuevo_valor)}
+        pub fn get_precio(&self, categoria: IdCategoria) -> Result<u128, ClubError> {self.club.get_precio(categoria)}
+        pub fn programar_precio(&mut self, categoria: IdCategoria, desde: Fecha, nuevo_valor: u128) -> Result<(), ClubError> {self.club.programar_precio(categoria, desde, nuevo_valor)}
+        pub fn get_precio_en(&self, categoria: IdCategoria, fecha: Fecha) -> Result<u128, ClubError> {self.club.get_precio_en(categoria, fecha)}
+        pub fn get_historial_precios(&self, categoria: IdCategoria) -> Result<Vec<(Fecha, u128)>, ClubError> {self.club.get_historial_precios(categoria)}
+        pub fn set_indexacion(&mut self, por_mil: u16, cada_meses: u8, desde: Fecha, multiplo: u128, redondeo: Redondeo) -> Result<(), ClubError> {self.club.set_indexacion(por_mil, cada_meses, desde, multiplo, redondeo)}
+        pub fn quitar_indexacion(&mut self) -> Result<(), ClubError> {self.club.quitar_indexacion()}
+        pub fn get_indexacion(&self) -> Option<Indexacion> {self.club.get_indexacion()}
+        pub fn registrar_actividad(&mut self, nombre: String) -> Result<IdActividad, ClubError> {self.club.registrar_actividad(nombre)}
+        pub fn get_actividades(&self) -> Vec<String> {self.club.get_actividades()}
+        pub fn registrar_categoria(&mut self, nombre: String, precio: u128, incluidas: Vec<IdActividad>, elegibles: Vec<IdActividad>, cantidad_a_elegir: u8) -> Result<IdCategoria, ClubError> {self.club.registrar_categoria(nombre, precio, incluidas, elegibles, cantidad_a_elegir)}
+        pub fn modificar_actividades_categoria(&mut self, id: IdCategoria, incluidas: Vec<IdActividad>, elegibles: Vec<IdActividad>, cantidad_a_elegir: u8) -> Result<(), ClubError> {self.club.modificar_actividades_categoria(id, incluidas, elegibles, cantidad_a_elegir)}
+        pub fn set_actividades_extra(&mut self, id: IdCategoria, maximo_a_elegir: u8, precio_por_actividad_extra: u128) -> Result<(), ClubError> {self.club.set_actividades_extra(id, maximo_a_elegir, precio_por_actividad_extra)}
+        pub fn get_categoria(&self, id: IdCategoria) -> Result<Categoria, ClubError> {self.club.get_categoria(id)}
+        pub fn get_categorias(&self) -> Vec<Categoria> {self.club.get_categorias()}
+        pub fn puede_acceder_a(&self, dni: u128, actividad: IdActividad) -> Result<bool, ClubError> {self.club.puede_acceder_a(dni, actividad)}
+        pub fn set_cantidad_pagos_bonificacion(&mut self, nuevo_valor:u16) -> Result<(), ClubError> {self.club.set_cantidad_pagos_bonificacion(nuevo_valor)}
         pub fn get_cantidad_pagos_bonificacion(&self) -> u16 {self.club.get_cantidad_pagos_bonificacion()}
-        pub fn set_porcentaje_bonificacion_pagos_consecutivos(&mut self, nuevo_valor: u8) {self.club.set_porcentaje_bonificacion_pagos_consecutivos(nuevo_valor)}
+        pub fn set_porcentaje_bonificacion_pagos_consecutivos(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {self.club.set_porcentaje_bonificacion_pagos_consecutivos(nuevo_valor)}
         pub fn get_porcentaje_bonificacion_pagos_consecutivos(&self) -> u8 {self.club.get_porcentaje_bonificacion_pagos_consecutivos()}
-        pub fn get_socios(&self) -> Vec<Socio> {self.club.get_socios()}
-        pub fn get_pagos(&self, socio: Option<u128>)-> Vec<Pago>{self.club.get_pagos(socio)}
-        pub fn registrar_nuevo_socio(&mut self, dni: u128, nombre: String, categoria:Categoria) {self.club.registrar_nuevo_socio(dni, nombre, categoria);}
-        pub fn get_socio(&self, id: u64) -> Option<Socio> {self.club.get_socio(id)}
-        pub fn realizar_pago(&mut self, dni: u128, monto: u128) {self.club.realizar_pago(dni, monto);}
+        pub fn get_socios(&self) -> Result<Vec<Socio>, ClubError> {self.club.get_socios()}
+        pub fn get_pagos(&self, socio: Option<u128>)-> Result<Vec<Pago>, ClubError> {self.club.get_pagos(socio)}
+        pub fn get_socios_pagina(&self, desde: u64, cantidad: u64, filtro: FiltroSocios) -> Result<(Vec<Socio>, Option<u64>), ClubError> {self.club.get_socios_pagina(desde, cantidad, filtro)}
+        pub fn get_pagos_pagina(&self, desde: u64, cantidad: u64, filtro: FiltroPagos) -> Result<(Vec<Pago>, Option<u64>), ClubError> {self.club.get_pagos_pagina(desde, cantidad, filtro)}
+        pub fn registrar_nuevo_socio(&mut self, dni: u128, nombre: String, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {self.club.registrar_nuevo_socio(dni, nombre, categoria, actividades_elegidas)}
+        pub fn importar_socios(&mut self, socios: Vec<SocioAImportar>) -> Result<Vec<Result<(), ClubError>>, ClubError> {self.club.importar_socios(socios)}
+        pub fn registrar_nuevo_socio_con_cupon(&mut self, dni: u128, nombre: String, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>, codigo: String) -> Result<(), ClubError> {self.club.registrar_nuevo_socio_con_cupon(dni, nombre, categoria, actividades_elegidas, codigo)}
+        pub fn get_socio(&self, id: u64) -> Result<Socio, ClubError> {self.club.get_socio(id)}
+        pub fn agregar_dependiente(&mut self, dni_titular: u128, dni: u128) -> Result<(), ClubError> {self.club.agregar_dependiente(dni_titular, dni)}
+        pub fn quitar_dependiente(&mut self, dni: u128) -> Result<(), ClubError> {self.club.quitar_dependiente(dni)}
+        pub fn set_porcentaje_descuento_familiar(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {self.club.set_porcentaje_descuento_familiar(nuevo_valor)}
+        pub fn get_porcentaje_descuento_familiar(&self) -> u8 {self.club.get_porcentaje_descuento_familiar()}
+        pub fn set_dias_primer_vencimiento(&mut self, dias: u16) -> Result<(), ClubError> {self.club.set_dias_primer_vencimiento(dias)}
+        pub fn get_dias_primer_vencimiento(&self) -> u16 {self.club.get_dias_primer_vencimiento()}
+        pub fn set_ciclo_facturacion(&mut self, ciclo: CicloFacturacion) -> Result<(), ClubError> {self.club.set_ciclo_facturacion(ciclo)}
+        pub fn get_ciclo_facturacion(&self) -> CicloFacturacion {self.club.get_ciclo_facturacion()}
+        pub fn cambiar_categoria(&mut self, dni: u128, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {self.club.cambiar_categoria(dni, categoria, actividades_elegidas)}
+        pub fn suspender_socio(&mut self, dni: u128) -> Result<(), ClubError> {self.club.suspender_socio(dni)}
+        pub fn reactivar_socio(&mut self, dni: u128) -> Result<(), ClubError> {self.club.reactivar_socio(dni)}
+        pub fn dar_de_baja_socio(&mut self, dni: u128) -> Result<(), ClubError> {self.club.dar_de_baja_socio(dni)}
+        pub fn readmitir_socio(&mut self, dni: u128, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {self.club.readmitir_socio(dni, categoria, actividades_elegidas)}
+        pub fn realizar_pago(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {self.club.realizar_pago(dni, monto)}
+        pub fn realizar_pago_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {self.club.realizar_pago_con_tokens(dni)}
+        pub fn vincular_cuenta(&mut self, dni: u128, cuenta: AccountId) -> Result<(), ClubError> {self.club.vincular_cuenta(dni, cuenta)}
+        pub fn reclamar_cuenta(&mut self, dni: u128) -> Result<(), ClubError> {self.club.reclamar_cuenta(dni)}
+        pub fn get_reclamo(&self, dni: u128) -> Option<AccountId> {self.club.get_reclamo(dni)}
+        pub fn aprobar_reclamo(&mut self, dni: u128) -> Result<(), ClubError> {self.club.aprobar_reclamo(dni)}
+        pub fn mi_socio(&self) -> Result<Socio, ClubError> {self.club.mi_socio()}
+        pub fn mi_pago_pendiente(&self) -> Result<Pago, ClubError> {self.club.mi_pago_pendiente()}
+        pub fn pagar_mi_cuota(&mut self) -> Result<(), ClubError> {self.club.pagar_mi_cuota()}
+        pub fn facturar(&mut self, cantidad: u64) -> Result<bool, ClubError> {self.club.facturar(cantidad)}
+        pub fn get_siguiente_socio_a_facturar(&self) -> u64 {self.club.get_siguiente_socio_a_facturar()}
+        pub fn estado_de_cuenta(&self, dni: u128) -> Result<EstadoDeCuenta, ClubError> {self.club.estado_de_cuenta(dni)}
+        pub fn realizar_pago_parcial(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {self.club.realizar_pago_parcial(dni, monto)}
+        pub fn realizar_pago_parcial_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {self.club.realizar_pago_parcial_con_tokens(dni)}
+        pub fn realizar_pago_adelantado(&mut self, dni: u128, periodos: u16, monto: u128) -> Result<(), ClubError> {self.club.realizar_pago_adelantado(dni, periodos, monto)}
+        pub fn realizar_pago_adelantado_con_tokens(&mut self, dni: u128, periodos: u16) -> Result<(), ClubError> {self.club.realizar_pago_adelantado_con_tokens(dni, periodos)}
+        pub fn get_monto_pago_adelantado(&self, dni: u128, periodos: u16) -> Result<u128, ClubError> {self.club.get_monto_pago_adelantado(dni, periodos)}
+        pub fn get_monto_a_pagar(&self, dni: u128) -> Result<u128, ClubError> {self.club.get_monto_a_pagar(dni)}
+        pub fn get_recargo_pendiente(&self, dni: u128) -> Result<u128, ClubError> {self.club.get_recargo_pendiente(dni)}
+        pub fn set_recargo(&mut self, recargo: Recargo) -> Result<(), ClubError> {self.club.set_recargo(recargo)}
+        pub fn get_recargo(&self) -> Recargo {self.club.get_recargo()}
+        pub fn set_porcentaje_descuento_pago_adelantado(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {self.club.set_porcentaje_descuento_pago_adelantado(nuevo_valor)}
+        pub fn get_porcentaje_descuento_pago_adelantado(&self) -> u8 {self.club.get_porcentaje_descuento_pago_adelantado()}
+        pub fn crear_cupon(&mut self, codigo: String, descuento: Descuento, desde: Fecha, hasta: Fecha, usos_maximos: u32, categorias: Vec<IdCategoria>) -> Result<(), ClubError> {self.club.crear_cupon(codigo, descuento, desde, hasta, usos_maximos, categorias)}
+        pub fn get_cupon(&self, codigo: String) -> Result<Cupon, ClubError> {self.club.get_cupon(codigo)}
+        pub fn aplicar_cupon(&mut self, dni: u128, codigo: String) -> Result<(), ClubError> {self.club.aplicar_cupon(dni, codigo)}
+        pub fn acreditar_saldo(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {self.club.acreditar_saldo(dni, monto)}
+        pub fn acreditar_saldo_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {self.club.acreditar_saldo_con_tokens(dni)}
+        pub fn get_saldo_a_favor(&self, dni: u128) -> Result<u128, ClubError> {self.club.get_saldo_a_favor(dni)}
+        pub fn revertir_pago(&mut self, dni: u128) -> Result<(), ClubError> {self.club.revertir_pago(dni)}
+        pub fn get_fondos(&self) -> Balance {self.club.get_fondos()}
+        pub fn retirar_fondos(&mut self, destinos: Vec<(AccountId, Balance)>) -> Result<(), ClubError> {self.club.retirar_fondos(destinos)}
     }
 }
 
@@ -187,13 +360,21 @@ mod tests {
     use std::collections::HashSet;
 
     use ink_env::{DefaultEnvironment};
-    use trabajo_final::trabajo_final::{Socio, Categoria::*, Actividad::*};
+    use trabajo_final::trabajo_final::{Socio, ClubError, Recargo, IdCategoria, IdActividad};
 
     use crate::trabajo_final_reporte::{TrabajoFinalReporte, ClubFalso};
 
-    fn generar<'a>() -> TrabajoFinalReporte {
+    // Las categorías y actividades con las que empieza el club
+    const CATEGORIA_A: IdCategoria = 0;
+    const CATEGORIA_B: IdCategoria = 1;
+    const CATEGORIA_C: IdCategoria = 2;
+    const GIMNASIO: IdActividad = 0;
+    const FUTBOL: IdActividad = 1;
+    const PADDLE: IdActividad = 7;
+
+    fn generar() -> TrabajoFinalReporte {
         let mut club = ClubFalso::new(ink_env::test::default_accounts::<DefaultEnvironment>().alice);
-        club.set_politica_autorizacion(false);
+        club.set_politica_autorizacion(false).unwrap();
         TrabajoFinalReporte::new(club)
     }
 
@@ -201,29 +382,29 @@ mod tests {
     fn obtener_socios_morosos_test() {
         let mut contrato = generar();
         // no hay morosos al principio
-        assert_eq!(contrato.obtener_socios_morosos(), vec![]);
-        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CategoriaA);
-        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CategoriaB(Paddle));
-        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CategoriaC);
+        assert_eq!(contrato.obtener_socios_morosos().unwrap(), vec![]);
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_B, vec![PADDLE]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
         
         // No son morosos al día de registrarse
-        assert_eq!(contrato.obtener_socios_morosos(), vec![]);
+        assert_eq!(contrato.obtener_socios_morosos().unwrap(), vec![]);
         contrato.club().avanzar_dias(9);
         // Sigue sin haber morosos porque no pasaron los 10 días
-        assert_eq!(contrato.obtener_socios_morosos(), vec![]);
+        assert_eq!(contrato.obtener_socios_morosos().unwrap(), vec![]);
         contrato.club().avanzar_dias(2);
         // Todos son morosos
-        let morosos = contrato.obtener_socios_morosos();
+        let morosos = contrato.obtener_socios_morosos().unwrap();
         let morosos: HashSet<u128> = HashSet::from_iter(morosos.iter().map(|s| s.get_dni()));
         assert!(morosos.contains(&0));
         assert!(morosos.contains(&1));
         assert!(morosos.contains(&2));
 
         // Bob ya no es moroso, por un mes
-        let monto = contrato.club().get_precio(CategoriaB(Paddle));
-        contrato.club().realizar_pago(1, monto);
+        let monto = contrato.club().get_precio(CATEGORIA_B).unwrap();
+        contrato.club().realizar_pago(1, monto).unwrap();
         contrato.club().avanzar_dias(29);
-        let morosos = contrato.obtener_socios_morosos();
+        let morosos = contrato.obtener_socios_morosos().unwrap();
         let morosos: HashSet<u128> = HashSet::from_iter(morosos.iter().map(|s| s.get_dni()));
         assert!(morosos.contains(&0));
         assert!(!morosos.contains(&1));
@@ -231,15 +412,15 @@ mod tests {
 
         contrato.club().avanzar_dias(2);
         // Todos son morosos otra vez
-        let morosos = contrato.obtener_socios_morosos();
+        let morosos = contrato.obtener_socios_morosos().unwrap();
         let morosos: HashSet<u128> = HashSet::from_iter(morosos.iter().map(|s| s.get_dni()));
         assert!(morosos.contains(&0));
         assert!(morosos.contains(&1));
         assert!(morosos.contains(&2));
     }
 
-    fn recaudacion(cat_a: u128, cat_b: u128, cat_c: u128) -> [u128; 4] {
-        [ cat_a, cat_b, cat_c, cat_a + cat_b + cat_c ]
+    fn recaudacion(cat_a: i128, cat_b: i128, cat_c: i128) -> Vec<i128> {
+        vec![ cat_a, cat_b, cat_c, cat_a + cat_b + cat_c ]
     }
     #[ink::test]
     fn informe_recaudacion_test() {
@@ -251,32 +432,115 @@ mod tests {
         let año = contrato.club().obtener_fecha_actual().get_año();
         let mes = contrato.club().obtener_fecha_actual().get_mes();
         // Al principio es 0
-        assert_eq!(contrato.informe_recaudacion(año, mes), recaudacion(0, 0, 0));
+        assert_eq!(contrato.informe_recaudacion(año, mes).unwrap(), recaudacion(0, 0, 0));
+        assert_eq!(contrato.informe_recaudacion(año, 13), Err(ClubError::ValorInvalido));
+        assert_eq!(contrato.informe_recaudacion(año, 0), Err(ClubError::ValorInvalido));
         // Setup de precios y socios
-        contrato.club().set_precio(CategoriaA, 3000);
-        contrato.club().set_precio(CategoriaB(Futbol), 2000);
-        contrato.club().set_precio(CategoriaC, 1000);
-        contrato.club().set_cantidad_pagos_bonificacion(1);
-        contrato.club().set_porcentaje_bonificacion_pagos_consecutivos(50);
-        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CategoriaA);
-        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CategoriaB(Paddle));
-        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CategoriaC);
-        contrato.club().registrar_nuevo_socio(3, "Dilan".into(), CategoriaC);
+        contrato.club().set_precio(CATEGORIA_A, 3000).unwrap();
+        contrato.club().set_precio(CATEGORIA_B, 2000).unwrap();
+        contrato.club().set_precio(CATEGORIA_C, 1000).unwrap();
+        contrato.club().set_cantidad_pagos_bonificacion(1).unwrap();
+        contrato.club().set_porcentaje_bonificacion_pagos_consecutivos(50).unwrap();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_B, vec![PADDLE]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(3, "Dilan".into(), CATEGORIA_C, vec![]).unwrap();
         // Todos pagan el primer mes
-        contrato.club().realizar_pago(0, 3000);
-        contrato.club().realizar_pago(1, 2000);
-        contrato.club().realizar_pago(2, 1000);
-        contrato.club().realizar_pago(3, 1000);
+        contrato.club().realizar_pago(0, 3000).unwrap();
+        contrato.club().realizar_pago(1, 2000).unwrap();
+        contrato.club().realizar_pago(2, 1000).unwrap();
+        contrato.club().realizar_pago(3, 1000).unwrap();
         
-        assert_eq!(contrato.informe_recaudacion(año, mes), recaudacion(3000, 2000, 2000));
+        assert_eq!(contrato.informe_recaudacion(año, mes).unwrap(), recaudacion(3000, 2000, 2000));
         // En el siguiente mes se recauda con descuentos
         contrato.club().avanzar_dias(30);
-        assert_eq!(contrato.informe_recaudacion(año, mes + 1), recaudacion(0, 0, 0));
-        contrato.club().realizar_pago(1, 1000);
-        contrato.club().realizar_pago(3, 500);
-        assert_eq!(contrato.informe_recaudacion(año, mes + 1), recaudacion(0, 1000, 500));
+        assert_eq!(contrato.informe_recaudacion(año, mes + 1).unwrap(), recaudacion(0, 0, 0));
+        contrato.club().realizar_pago(1, 1000).unwrap();
+        contrato.club().realizar_pago(3, 500).unwrap();
+        assert_eq!(contrato.informe_recaudacion(año, mes + 1).unwrap(), recaudacion(0, 1000, 500));
         // Las recaudaciones del primer mes siguen estando
-        assert_eq!(contrato.informe_recaudacion(año, mes), recaudacion(3000, 2000, 2000));
+        assert_eq!(contrato.informe_recaudacion(año, mes).unwrap(), recaudacion(3000, 2000, 2000));
+        // Aunque los socios cambien de categoría
+        contrato.club().cambiar_categoria(2, CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(contrato.informe_recaudacion(año, mes).unwrap(), recaudacion(3000, 2000, 2000));
+        // Los pagos parciales cuentan aunque el pago no esté completo
+        contrato.club().realizar_pago_parcial(0, 100).unwrap();
+        assert_eq!(contrato.informe_recaudacion(año, mes + 1).unwrap(), recaudacion(100, 1000, 500));
+    }
+
+    #[ink::test]
+    fn informe_recaudacion_con_recargos_test() {
+        let mut contrato = generar();
+        contrato.club().set_precio(CATEGORIA_C, 1000).unwrap();
+        contrato.club().set_recargo(Recargo::Porcentaje(10)).unwrap();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().avanzar_dias(15);
+        let monto = contrato.club().get_monto_a_pagar(0).unwrap();
+        assert_eq!(monto, 1100);
+        contrato.club().realizar_pago(0, monto).unwrap();
+        let fecha = contrato.club().obtener_fecha_actual();
+        assert_eq!(contrato.informe_recaudacion(fecha.get_año(), fecha.get_mes()).unwrap(), recaudacion(0, 0, 1100));
+    }
+
+    #[ink::test]
+    fn informe_recaudacion_con_pagos_revertidos_test() {
+        let mut contrato = generar();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_C, vec![]).unwrap();
+        let precio_a = contrato.club().get_precio(CATEGORIA_A).unwrap();
+        let precio_c = contrato.club().get_precio(CATEGORIA_C).unwrap();
+        contrato.club().realizar_pago(0, precio_a).unwrap();
+        contrato.club().realizar_pago(1, precio_c).unwrap();
+        let fecha = contrato.club().obtener_fecha_actual();
+        // Revertido en el mismo mes, es como si no se hubiera pagado
+        contrato.club().revertir_pago(1).unwrap();
+        assert_eq!(contrato.informe_recaudacion(fecha.get_año(), fecha.get_mes()).unwrap(), recaudacion(precio_a as i128, 0, 0));
+
+        // Revertido en el mes siguiente, se descuenta en ese mes aunque no se haya recaudado nada
+        contrato.club().avanzar_dias(40);
+        contrato.club().revertir_pago(0).unwrap();
+        let siguiente = contrato.club().obtener_fecha_actual();
+        assert_eq!(contrato.informe_recaudacion(fecha.get_año(), fecha.get_mes()).unwrap(), recaudacion(precio_a as i128, 0, 0));
+        assert_eq!(contrato.informe_recaudacion(siguiente.get_año(), siguiente.get_mes()).unwrap(), recaudacion(-(precio_a as i128), 0, 0));
+        contrato.club().realizar_pago(0, precio_a).unwrap();
+        assert_eq!(contrato.informe_recaudacion(siguiente.get_año(), siguiente.get_mes()).unwrap(), recaudacion(0, 0, 0));
+    }
+
+    #[ink::test]
+    fn socios_con_saldo_a_favor_test() {
+        let mut contrato = generar();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(contrato.socios_con_saldo_a_favor().unwrap(), vec![]);
+        contrato.club().acreditar_saldo(1, 500).unwrap();
+        let socios = contrato.socios_con_saldo_a_favor().unwrap();
+        assert_eq!(socios_a_dni(socios.clone()), vec![1]);
+        assert_eq!(socios[0].get_saldo_a_favor(), 500);
+        // Cuando se usa todo el saldo ya no aparece
+        let precio = contrato.club().get_precio(CATEGORIA_C).unwrap();
+        contrato.club().realizar_pago(1, precio).unwrap();
+        assert_eq!(contrato.socios_con_saldo_a_favor().unwrap(), vec![]);
+    }
+
+    #[ink::test]
+    fn informe_deudas_test() {
+        let mut contrato = generar();
+        assert_eq!(contrato.informe_deudas().unwrap(), vec![]);
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(3, "Dilan".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().agregar_dependiente(1, 2).unwrap();
+        contrato.club().dar_de_baja_socio(3).unwrap();
+
+        contrato.club().avanzar_dias(40);
+        contrato.club().facturar(10).unwrap();
+        // El grupo familiar aparece una vez, y el socio dado de baja no adeuda nada
+        let estados = contrato.informe_deudas().unwrap();
+        let deudas: Vec<(u128, u128)> = estados.iter().map(|estado| (estado.get_dni(), estado.get_deuda_total())).collect();
+        assert_eq!(deudas, vec![(0, 10000), (1, 8000)]);
+        assert_eq!(estados[0].get_periodos().len(), 2);
+        assert_eq!(estados[0].get_periodos()[0].get_dias_de_atraso(), 30);
     }
 
     fn socios_a_dni(socios: Vec<Socio>) -> Vec<u128> {
@@ -285,30 +549,94 @@ mod tests {
         socios
     }
     
+    #[ink::test]
+    fn reportes_con_socios_inactivos_test() {
+        let mut contrato = generar();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_B, vec![FUTBOL]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().suspender_socio(1).unwrap();
+        contrato.club().dar_de_baja_socio(2).unwrap();
+        // Los inactivos no acceden a las actividades
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap()), vec![0]);
+        // Ni son morosos
+        contrato.club().avanzar_dias(15);
+        assert_eq!(socios_a_dni(contrato.obtener_socios_morosos().unwrap()), vec![0]);
+        // Bob vuelve y tiene otros 10 días para pagar
+        contrato.club().reactivar_socio(1).unwrap();
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![1]);
+        contrato.club().avanzar_dias(11);
+        assert_eq!(socios_a_dni(contrato.obtener_socios_morosos().unwrap()), vec![0, 1]);
+    }
+
+    #[ink::test]
+    fn reportes_con_grupos_familiares_test() {
+        let mut contrato = generar();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_B, vec![FUTBOL]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
+        contrato.club().agregar_dependiente(0, 1).unwrap();
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![0, 1]);
+
+        // Si el titular no paga, todo el grupo es moroso
+        contrato.club().avanzar_dias(11);
+        assert_eq!(socios_a_dni(contrato.obtener_socios_morosos().unwrap()), vec![0, 1, 2]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap()), vec![]);
+
+        // Y cuando paga, ninguno
+        let monto = contrato.club().get_monto_a_pagar(0).unwrap();
+        contrato.club().realizar_pago(0, monto).unwrap();
+        assert_eq!(socios_a_dni(contrato.obtener_socios_morosos().unwrap()), vec![2]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![0, 1]);
+    }
+
+    #[ink::test]
+    fn reportes_con_categorias_nuevas_test() {
+        let mut contrato = generar();
+        let voley = contrato.club().registrar_actividad("Vóley".into()).unwrap();
+        let juvenil = contrato.club().registrar_categoria("Juvenil".into(), 700, vec![voley], vec![FUTBOL, PADDLE], 1).unwrap();
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), juvenil, vec![PADDLE]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(voley).unwrap()), vec![0]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(PADDLE).unwrap()), vec![0, 1]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap()), vec![1]);
+
+        // La categoría nueva tiene su lugar en el informe, antes del total
+        contrato.club().realizar_pago(0, 700).unwrap();
+        let fecha = contrato.club().obtener_fecha_actual();
+        assert_eq!(contrato.informe_recaudacion(fecha.get_año(), fecha.get_mes()).unwrap(), vec![0, 0, 0, 700, 700]);
+    }
+
     #[ink::test]
     fn socios_no_morosos_en_actividad_test() {
         let mut contrato = generar();
         // Al principio no hay nadie
-        assert_eq!(contrato.socios_no_morosos_en_actividad(Gimnasio), vec![]);
-        assert_eq!(contrato.socios_no_morosos_en_actividad(Futbol), vec![]);
-        assert_eq!(contrato.socios_no_morosos_en_actividad(Paddle), vec![]);
+        assert_eq!(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap(), vec![]);
+        assert_eq!(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap(), vec![]);
+        assert_eq!(contrato.socios_no_morosos_en_actividad(PADDLE).unwrap(), vec![]);
 
-        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CategoriaA);
-        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CategoriaB(Futbol));
-        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CategoriaC);
+        contrato.club().registrar_nuevo_socio(0, "Alicia".into(), CATEGORIA_A, vec![]).unwrap();
+        contrato.club().registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_B, vec![FUTBOL]).unwrap();
+        contrato.club().registrar_nuevo_socio(2, "Carlos".into(), CATEGORIA_C, vec![]).unwrap();
 
         
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Gimnasio)), vec![0, 1, 2]);
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Futbol)), vec![0, 1]);
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Paddle)), vec![0]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap()), vec![0, 1, 2]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![0, 1]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(PADDLE).unwrap()), vec![0]);
         
         // Pasan los 10 días y sólo Bob paga
         contrato.club().avanzar_dias(15);
-        let monto = contrato.club().get_precio(CategoriaB(Futbol));
-        contrato.club().realizar_pago(1, monto);
+        let monto = contrato.club().get_precio(CATEGORIA_B).unwrap();
+        contrato.club().realizar_pago(1, monto).unwrap();
+
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(GIMNASIO).unwrap()), vec![1]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![1]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(PADDLE).unwrap()), vec![]);
 
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Gimnasio)), vec![1]);
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Futbol)), vec![1]);
-        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(Paddle)), vec![]);
+        // Un socio que eligió varios deportes puede acceder a todos
+        contrato.club().set_actividades_extra(CATEGORIA_B, 2, 500).unwrap();
+        contrato.club().registrar_nuevo_socio(3, "Dilan".into(), CATEGORIA_B, vec![FUTBOL, PADDLE]).unwrap();
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(FUTBOL).unwrap()), vec![1, 3]);
+        assert_eq!(socios_a_dni(contrato.socios_no_morosos_en_actividad(PADDLE).unwrap()), vec![3]);
     }
 }
\ No newline at end of file
@@ -22,6 +22,7 @@ impl Fecha {
     ///assert!(Fecha::new(1,12,-2051).is_ok());
     ///```
     // No se permite construir fechas inválidas
+    #[allow(clippy::result_unit_err)]
     pub fn new(dia: i8, mes: i8, año: i32) -> Result<Fecha, ()> {
         let fecha_tentativa = Fecha {dia, mes, año};
         if fecha_tentativa.es_fecha_valida() {
@@ -187,6 +188,42 @@ impl Fecha {
         self.dia -= dias as i8;
     }
     
+    /// Suma a la fecha los meses ingresados como parametro, manteniendo el dia.
+    /// Si el mes resultante es más corto, la fecha queda en su último dia.
+    /// 
+    /// Ejemplo
+    /// 
+    /// ```
+    /// use trabajo_final::fecha::Fecha;
+    /// let mut f = Fecha::new(31, 1, 2024).unwrap();
+    /// f.sumar_meses(1);
+    /// assert!(f.igual_que(&Fecha::new(29, 2, 2024).unwrap()));
+    /// f.sumar_meses(11);
+    /// assert!(f.igual_que(&Fecha::new(29, 1, 2025).unwrap()));
+    /// ```
+    pub fn sumar_meses(&mut self, meses: i32) {
+        let total = self.año * 12 + (self.mes as i32 - 1) + meses;
+        self.año = total.div_euclid(12);
+        self.mes = total.rem_euclid(12) as i8 + 1;
+        self.ajustar_dia(self.dia);
+    }
+
+    /// Cambia el dia de la fecha por el ingresado como parametro, o por el último dia del mes si el mes es más corto.
+    /// 
+    /// Ejemplo
+    /// 
+    /// ```
+    /// use trabajo_final::fecha::Fecha;
+    /// let mut f = Fecha::new(10, 4, 2021).unwrap();
+    /// f.ajustar_dia(31);
+    /// assert_eq!(f.get_dia(), 30);
+    /// f.ajustar_dia(5);
+    /// assert_eq!(f.get_dia(), 5);
+    /// ```
+    pub fn ajustar_dia(&mut self, dia: i8) {
+        self.dia = dia.clamp(1, self.ultimo_dia_mes());
+    }
+
     /// Determina si la fecha actual es mayor a la ingresada por parametro.
     /// 
     /// Ejemplo
@@ -206,6 +243,32 @@ impl Fecha {
         if self.dia > otra.dia {return true}
         false
     }
+    /// Retorna la cantidad de dias desde la fecha actual hasta la ingresada por parametro,
+    /// que es negativa si la ingresada es anterior.
+    /// 
+    /// Ejemplo
+    /// ```
+    /// use trabajo_final::fecha::Fecha;
+    /// let f = Fecha::new(10, 10, 2021).unwrap();
+    /// assert_eq!(f.dias_hasta(&Fecha::new(9, 11, 2021).unwrap()), 30);
+    /// assert_eq!(f.dias_hasta(&Fecha::new(30, 9, 2021).unwrap()), -10);
+    /// ```
+    pub fn dias_hasta(&self, otra: &Self) -> i32 {
+        (otra.numero_de_dia() - self.numero_de_dia()) as i32
+    }
+
+    /// Retorna la cantidad de dias desde el 1/3/0000 hasta la fecha.
+    fn numero_de_dia(&self) -> i64 {
+        // Se cuenta el año desde marzo, para que el 29 de febrero quede al final
+        let (año, mes) = if self.mes <= 2 {
+            (self.año as i64 - 1, self.mes as i64 + 9)
+        } else {
+            (self.año as i64, self.mes as i64 - 3)
+        };
+        let dia_del_año = (153 * mes + 2) / 5 + self.dia as i64 - 1;
+        año * 365 + año.div_euclid(4) - año.div_euclid(100) + año.div_euclid(400) + dia_del_año
+    }
+
     /// Retorna true si la fecha actual es igual a la ingresada como parametro, falso en caso contrario.
     pub fn igual_que(&self, otra: &Self) -> bool {
         self.año == otra.año && self.mes == otra.mes && self.dia == otra.dia
@@ -282,9 +345,11 @@ fn fecha_test() {
         (3584, (25, 5, 2023)),
     ];
     for suma in sumas.iter() {
-        let orig = fecha_inicial.clone();
+        let orig = fecha_inicial;
         let resultado = Fecha::new(suma.1.0, suma.1.1, suma.1.2).unwrap();
         fecha_inicial.sumar_dias(suma.0);
+        assert_eq!(orig.dias_hasta(&fecha_inicial), suma.0);
+        assert_eq!(fecha_inicial.dias_hasta(&orig), -suma.0);
         assert!(fecha_inicial.igual_que(&resultado), "{orig:?} + {} dias, se esperaba {resultado:?} pero dio {fecha_inicial:?}", suma.0);
     }
     for resta in sumas.iter().rev() {
@@ -293,4 +358,17 @@ fn fecha_test() {
         fecha_inicial.restar_dias(resta.0);
     }
     assert!(fecha_inicial.igual_que(&Fecha::new(1, 1, 1999).unwrap()));
+
+    let mut fecha = Fecha::new(31, 10, 2023).unwrap();
+    let meses = [
+        (1, (30, 11, 2023)),
+        (3, (29, 2, 2024)),
+        (12, (28, 2, 2025)),
+        (-14, (28, 12, 2023)),
+        (0, (28, 12, 2023)),
+    ];
+    for (n, resultado) in meses {
+        fecha.sumar_meses(n);
+        assert!(fecha.igual_que(&Fecha::new(resultado.0, resultado.1, resultado.2).unwrap()), "se esperaba {resultado:?} pero dio {fecha:?}");
+    }
 }
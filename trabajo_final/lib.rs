@@ -8,39 +8,403 @@ pub mod trabajo_final {
     use crate::fecha::Fecha;
     use ink::prelude::string::String;
     use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
 
     /*
     Notas:
-        id_socio, en cualquier contexto, es el índice del socio en el vector de socios (empieza en 0)
+        id_socio, en cualquier contexto, es el número de registro del socio (empieza en 0), y su clave en el Mapping de socios
+        id_pago es el número de registro del pago (empieza en 0), y su clave en el Mapping de pagos
+        Los socios y pagos se guardan en Mappings para no tener que leer y escribir todos en cada llamada
     */
 
 
     #[ink(storage)]    
-    #[derive(scale::Encode, Clone)]
     pub struct Club {
         /// Nombre del club.
         nombre: String,
-        /// Pagos registrados en el club, ya sea pagados o pendientes.
-        pagos: Vec<Pago>,
-        /// Socios del club.
-        socios: Vec<Socio>,
-        /// Precio de cada categoría, en tokens por mes
-        precios: [u128; 3],
+        /// Pagos registrados en el club, ya sea pagados o pendientes, por id de pago.
+        pagos: Mapping<u64, Pago>,
+        /// Cantidad de pagos registrados (también es el id del próximo pago).
+        cantidad_pagos: u64,
+        /// Socios del club, por id de socio.
+        socios: Mapping<u64, Socio>,
+        /// Cantidad de socios registrados (también es el id del próximo socio).
+        cantidad_socios: u64,
+        /// Id del socio con cada dni.
+        id_por_dni: Mapping<u128, u64>,
+        /// Ids de los pagos de cada socio, en orden: (id_socio, n) => id del n-ésimo pago del socio.
+        pagos_por_socio: Mapping<(u64, u32), u64>,
+        /// Cantidad de pagos de cada socio.
+        cantidad_pagos_por_socio: Mapping<u64, u32>,
+        /// Id del pago pendiente de cada socio.
+        pago_pendiente: Mapping<u64, u64>,
+        /// Ids de los pagos impagos que la facturación generó a cada socio después del pendiente, en orden.
+        pagos_facturados: Mapping<u64, Vec<u64>>,
+        /// Id del próximo socio a facturar en la facturación en curso (ver [Club::facturar]).
+        siguiente_socio_a_facturar: u64,
+        /// Id del socio vinculado a cada cuenta.
+        id_por_cuenta: Mapping<AccountId, u64>,
+        /// Cuenta que reclamó cada dni, hasta que se apruebe el reclamo.
+        reclamos: Mapping<u128, AccountId>,
+        /// Nombre de cada actividad, por id de actividad.
+        actividades: Vec<String>,
+        /// Cada categoría con su precio y sus actividades, por id de categoría.
+        categorias: Vec<Categoria>,
         /// Cantidad de pagos no morosos consecutivos necesarios para conseguir el descuento.
         // 65535 meses suena como un máximo razonable...
         cantidad_pagos_bonificacion: u16,
         /// Porcentaje del descuento por pagos no morosos consecutivos.
         // el máximo es 100, así que con u8 sobra
         porcentaje_bonificacion: u8,
+        /// Porcentaje del descuento para los pagos que se pagan por adelantado.
+        porcentaje_descuento_adelantado: u8,
+        /// Porcentaje del descuento para los pagos de los grupos familiares con al menos un dependiente activo.
+        porcentaje_descuento_familiar: u8,
+        /// Recargo que se cobra por los pagos que se pagan después del vencimiento.
+        recargo: Recargo,
+        /// Días que tiene un socio nuevo o readmitido para hacer su primer pago.
+        dias_primer_vencimiento: u16,
+        /// Cada cuánto vencen los pagos.
+        ciclo_facturacion: CicloFacturacion,
+        /// Cupones de descuento, por código.
+        cupones: Mapping<String, Cupon>,
+        /// Ajuste automático de los precios de las categorías, si hay.
+        indexacion: Option<Indexacion>,
         
         // permisos, etc.
-        /// Si es true, se utiliza la política "cerrada" donde sólo los usuarios autorizados pueden realizar cambios.
+        /// Si es true, se utiliza la política "cerrada" donde sólo el dueño y las cuentas con el [Rol] correspondiente pueden realizar cada operación.
         /// Si es false, la política es "abierta" y todos los usuarios pueden realizar la mayoría de operaciones (excepto cerrar la política).
         politica_autorizacion: bool,
         /// La cuenta que se considera dueña del club.
         dueño: AccountId,
-        /// Las cuentas autorizadas a realizar cambios con la política cerrada.
-        autorizados: Vec<AccountId>,
+        /// La cuenta a la que el dueño le propuso pasarle el club, que todavía no aceptó.
+        dueño_propuesto: Option<AccountId>,
+        /// Los roles otorgados a cada cuenta con la política cerrada. Una cuenta puede tener varios roles.
+        roles: Vec<(AccountId, Rol)>,
+
+        /// Tokens recaudados con pagos reales que todavía no fueron retirados por el dueño.
+        fondos: Balance,
+    }
+
+    // Eventos
+    // Los que refieren a un socio tienen su dni como topic, y los que refieren a una cuenta la tienen como topic,
+    // para que se puedan filtrar desde afuera del contrato.
+
+    /// Se emite al registrarse un nuevo socio.
+    #[ink(event)]
+    pub struct SocioRegistrado {
+        #[ink(topic)]
+        dni: u128,
+        categoria: IdCategoria,
+        /// La cuenta que registró al socio.
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando una cuenta reclama ser la de un socio.
+    #[ink(event)]
+    pub struct CuentaReclamada {
+        #[ink(topic)]
+        dni: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al vincular una cuenta con un socio.
+    #[ink(event)]
+    pub struct CuentaVinculada {
+        #[ink(topic)]
+        dni: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando un socio se suspende, se reactiva, se da de baja o se readmite.
+    #[ink(event)]
+    pub struct EstadoSocioCambiado {
+        #[ink(topic)]
+        dni: u128,
+        estado: EstadoSocio,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando un socio cambia de categoría.
+    #[ink(event)]
+    pub struct CategoriaCambiada {
+        #[ink(topic)]
+        dni: u128,
+        categoria_anterior: IdCategoria,
+        categoria_nueva: IdCategoria,
+        /// El monto del pago pendiente, recalculado.
+        monto_pendiente: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al agregar un socio como dependiente del grupo familiar de otro.
+    #[ink(event)]
+    pub struct DependienteAgregado {
+        #[ink(topic)]
+        dni_titular: u128,
+        #[ink(topic)]
+        dni: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando un dependiente deja el grupo familiar de su titular.
+    #[ink(event)]
+    pub struct DependienteQuitado {
+        #[ink(topic)]
+        dni_titular: u128,
+        #[ink(topic)]
+        dni: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al registrarse un pago parcial que no llega a cubrir el pago pendiente de un socio.
+    #[ink(event)]
+    pub struct PagoParcialRealizado {
+        #[ink(topic)]
+        dni: u128,
+        monto: u128,
+        /// Lo que falta pagar para cubrir el pago pendiente.
+        restante: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al registrarse el pago de un socio.
+    #[ink(event)]
+    pub struct PagoRealizado {
+        #[ink(topic)]
+        dni: u128,
+        monto: u128,
+        fecha: Fecha,
+        a_tiempo: bool,
+        /// La cuenta que registró el pago.
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al sumar saldo a favor a un socio.
+    #[ink(event)]
+    pub struct SaldoAcreditado {
+        #[ink(topic)]
+        dni: u128,
+        monto: u128,
+        /// El saldo a favor total del socio.
+        saldo: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al revertir un pago.
+    #[ink(event)]
+    pub struct PagoRevertido {
+        #[ink(topic)]
+        dni: u128,
+        /// El monto que se había pagado, con el recargo.
+        monto: u128,
+        /// Los tokens que se devolvieron.
+        reembolso: Balance,
+        /// La cuenta que revirtió el pago.
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando un socio consigue la bonificación por pagos consecutivos en su próximo pago.
+    #[ink(event)]
+    pub struct BonificacionOtorgada {
+        #[ink(topic)]
+        dni: u128,
+        /// El monto del próximo pago, ya con el descuento aplicado.
+        monto: u128,
+        vencimiento: Fecha,
+    }
+
+    /// Se emite al registrar una nueva actividad.
+    #[ink(event)]
+    pub struct ActividadRegistrada {
+        id: IdActividad,
+        nombre: String,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al registrar una nueva categoría.
+    #[ink(event)]
+    pub struct CategoriaRegistrada {
+        id: IdCategoria,
+        nombre: String,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar las actividades de una categoría.
+    #[ink(event)]
+    pub struct CategoriaModificada {
+        id: IdCategoria,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el precio de una categoría, ya sea desde hoy o programado para más adelante.
+    #[ink(event)]
+    pub struct PrecioCambiado {
+        categoria: IdCategoria,
+        /// La fecha desde la que rige el nuevo precio.
+        desde: Fecha,
+        /// El precio que iba a regir en esa fecha.
+        precio_anterior: u128,
+        precio_nuevo: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar la configuración de la bonificación por pagos consecutivos.
+    #[ink(event)]
+    pub struct BonificacionCambiada {
+        cantidad_pagos_bonificacion: u16,
+        porcentaje_bonificacion: u8,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el porcentaje de descuento para pagos adelantados.
+    #[ink(event)]
+    pub struct DescuentoPagoAdelantadoCambiado {
+        porcentaje: u8,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el porcentaje de descuento para grupos familiares.
+    #[ink(event)]
+    pub struct DescuentoFamiliarCambiado {
+        porcentaje: u8,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el recargo por pagos atrasados.
+    #[ink(event)]
+    pub struct RecargoCambiado {
+        recargo: Recargo,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al establecer o quitar la indexación de los precios.
+    #[ink(event)]
+    pub struct IndexacionCambiada {
+        indexacion: Option<Indexacion>,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite cuando la facturación genera los pagos de los períodos que ya empezaron de un socio.
+    #[ink(event)]
+    pub struct PagosFacturados {
+        #[ink(topic)]
+        dni: u128,
+        cantidad: u32,
+        monto: u128,
+    }
+
+    /// Se emite al cambiar el vencimiento del primer pago o el ciclo de facturación.
+    #[ink(event)]
+    pub struct FacturacionCambiada {
+        dias_primer_vencimiento: u16,
+        ciclo_facturacion: CicloFacturacion,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al crear un cupón de descuento.
+    #[ink(event)]
+    pub struct CuponCreado {
+        codigo: String,
+        descuento: Descuento,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al usar un cupón de descuento en el pago pendiente de un socio.
+    #[ink(event)]
+    pub struct CuponAplicado {
+        #[ink(topic)]
+        dni: u128,
+        codigo: String,
+        /// El monto que se descontó del pago.
+        descuento: u128,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el nombre del club.
+    #[ink(event)]
+    pub struct NombreCambiado {
+        nombre: String,
+        #[ink(topic)]
+        cuenta: AccountId,
+    }
+
+    /// Se emite al cambiar el dueño del club.
+    #[ink(event)]
+    pub struct DueñoCambiado {
+        #[ink(topic)]
+        anterior: AccountId,
+        #[ink(topic)]
+        nuevo: AccountId,
+    }
+
+    /// Se emite cuando el dueño le propone el club a otra cuenta.
+    #[ink(event)]
+    pub struct DueñoPropuesto {
+        #[ink(topic)]
+        dueño: AccountId,
+        #[ink(topic)]
+        propuesto: AccountId,
+    }
+
+    /// Se emite cuando se cancela la propuesta de un nuevo dueño.
+    #[ink(event)]
+    pub struct PropuestaDeDueñoCancelada {
+        #[ink(topic)]
+        propuesto: AccountId,
+    }
+
+    /// Se emite al otorgarle un rol a una cuenta.
+    #[ink(event)]
+    pub struct RolOtorgado {
+        #[ink(topic)]
+        cuenta: AccountId,
+        rol: Rol,
+    }
+
+    /// Se emite al revocarle un rol a una cuenta.
+    #[ink(event)]
+    pub struct RolRevocado {
+        #[ink(topic)]
+        cuenta: AccountId,
+        rol: Rol,
+    }
+
+    /// Se emite al transferir fondos recaudados a una cuenta.
+    #[ink(event)]
+    pub struct FondosRetirados {
+        #[ink(topic)]
+        destino: AccountId,
+        monto: Balance,
+    }
+
+    /// Se emite al cambiar la política de autorización.
+    #[ink(event)]
+    pub struct PoliticaAutorizacionCambiada {
+        usar_la_politica: bool,
     }
 
     impl Club {
@@ -50,26 +414,60 @@ pub mod trabajo_final {
             Self::_new(dueño)
         }
         fn _new(dueño: AccountId) -> Self {
-            Self { 
+            let mut club = Self { 
                 nombre: "Seminario Rust".into(),
-                pagos : Vec::new(),
-                socios: Vec::new(),
-                precios: [5000, 3000, 2000],
+                pagos: Mapping::default(),
+                cantidad_pagos: 0,
+                socios: Mapping::default(),
+                cantidad_socios: 0,
+                id_por_dni: Mapping::default(),
+                pagos_por_socio: Mapping::default(),
+                cantidad_pagos_por_socio: Mapping::default(),
+                pago_pendiente: Mapping::default(),
+                pagos_facturados: Mapping::default(),
+                siguiente_socio_a_facturar: 0,
+                id_por_cuenta: Mapping::default(),
+                reclamos: Mapping::default(),
+                actividades: ["Gimnasio", "Fútbol", "Básquet", "Rugby", "Hockey", "Natación", "Tenis", "Paddle"]
+                    .iter().map(|&nombre| nombre.into()).collect(),
+                categorias: Vec::new(),
                 cantidad_pagos_bonificacion: 5,
                 porcentaje_bonificacion: 10,
+                porcentaje_descuento_adelantado: 0,
+                porcentaje_descuento_familiar: 0,
+                recargo: Recargo::Ninguno,
+                dias_primer_vencimiento: 10,
+                ciclo_facturacion: CicloFacturacion::Dias(30),
+                cupones: Mapping::default(),
+                indexacion: None,
                 politica_autorizacion: true,
                 dueño,
-                autorizados: Vec::new(),
-            }
+                dueño_propuesto: None,
+                roles: Vec::new(),
+                fondos: 0,
+            };
+            // Las categorías con las que empieza el club: A con todas las actividades,
+            // B con el gimnasio y un deporte a elección, y C sólo con el gimnasio.
+            let hoy = club._obtener_fecha_actual();
+            club.categorias = ink::prelude::vec![
+                Categoria::new("A".into(), 5000, (0..8).collect(), Vec::new(), 0, hoy),
+                Categoria::new("B".into(), 3000, ink::prelude::vec![0], (1..8).collect(), 1, hoy),
+                Categoria::new("C".into(), 2000, ink::prelude::vec![0], Vec::new(), 0, hoy),
+            ];
+            club
         }
 
         /// Cambia la política de autorización a cerrada (true) o abierta (false).
         /// 
         /// Sólo el dueño puede cambiar la política, para evitar que alguien la cierre accidentalmente antes de pasarse el dueño.
         #[ink(message)]
-        pub fn set_politica_autorizacion(&mut self, usar_la_politica: bool) {
-            assert!(self.soy_el_dueño(), "Sólo el dueño puede cambiar la política de autorización.");
+        pub fn set_politica_autorizacion(&mut self, usar_la_politica: bool) -> Result<(), ClubError> {
+            if !self.soy_el_dueño() {
+                return Err(ClubError::NoEsDueño);
+            }
             self.politica_autorizacion = usar_la_politica;
+            self.env().emit_event(PoliticaAutorizacionCambiada { usar_la_politica });
+            Ok(())
         }
         
         /// Retorna true si la cuenta actual es dueña del club, o false en caso contrario.
@@ -78,17 +476,74 @@ pub mod trabajo_final {
             self.dueño == Self::env().caller()
         }
 
-        /// Retorna true si la cuenta actual está autorizada (con la política abierta, todos están autorizados), o false en caso contrario.
+        /// Retorna true si la cuenta actual puede realizar las operaciones del [Rol] dado, o false en caso contrario.
+        /// 
+        /// El dueño puede realizar todas las operaciones, y con la política abierta todos pueden.
+        #[ink(message)]
+        pub fn tengo_rol(&self, rol: Rol) -> bool {
+            !self.politica_autorizacion || self.soy_el_dueño() || self.roles.contains(&(Self::env().caller(), rol))
+        }
+
+        /// Retorna [ClubError::NoAutorizado] si la cuenta actual no tiene el rol dado.
+        fn verificar_rol(&self, rol: Rol) -> Result<(), ClubError> {
+            if !self.tengo_rol(rol) {
+                return Err(ClubError::NoAutorizado);
+            }
+            Ok(())
+        }
+
+        /// Retorna [ClubError::NoEsDueño] si la política es cerrada y la cuenta actual no es la dueña.
+        fn verificar_dueño(&self) -> Result<(), ClubError> {
+            if self.politica_autorizacion && !self.soy_el_dueño() {
+                return Err(ClubError::NoEsDueño);
+            }
+            Ok(())
+        }
+
+        /// Le propone el club a la cuenta ingresada por parametro, que pasa a ser la dueña recién cuando acepta con [Club::aceptar_dueño].
+        /// Si ya había otra propuesta, se reemplaza.
+        /// 
+        /// Sólo el dueño puede proponer un nuevo dueño, incluso con la política abierta.
+        #[ink(message)]
+        pub fn proponer_dueño(&mut self, nuevo_dueño: AccountId) -> Result<(), ClubError> {
+            if !self.soy_el_dueño() {
+                return Err(ClubError::NoEsDueño);
+            }
+            self.dueño_propuesto = Some(nuevo_dueño);
+            self.env().emit_event(DueñoPropuesto { dueño: self.dueño, propuesto: nuevo_dueño });
+            Ok(())
+        }
+
+        /// La cuenta propuesta acepta ser la nueva dueña del club.
+        #[ink(message)]
+        pub fn aceptar_dueño(&mut self) -> Result<(), ClubError> {
+            let propuesto = self.dueño_propuesto.ok_or(ClubError::SinPropuestaDeDueño)?;
+            if propuesto != self.env().caller() {
+                return Err(ClubError::NoEsDueñoPropuesto);
+            }
+            let anterior = self.dueño;
+            self.dueño = propuesto;
+            self.dueño_propuesto = None;
+            self.env().emit_event(DueñoCambiado { anterior, nuevo: propuesto });
+            Ok(())
+        }
+
+        /// Cancela la propuesta de un nuevo dueño. La puede cancelar el dueño o la cuenta propuesta (para rechazarla).
         #[ink(message)]
-        pub fn estoy_autorizado(&self) -> bool {
-            !self.politica_autorizacion || self.soy_el_dueño() || self.autorizados.contains(&Self::env().caller())
+        pub fn cancelar_propuesta(&mut self) -> Result<(), ClubError> {
+            let propuesto = self.dueño_propuesto.ok_or(ClubError::SinPropuestaDeDueño)?;
+            if !self.soy_el_dueño() && propuesto != self.env().caller() {
+                return Err(ClubError::NoEsDueño);
+            }
+            self.dueño_propuesto = None;
+            self.env().emit_event(PropuestaDeDueñoCancelada { propuesto });
+            Ok(())
         }
 
-        /// Cambia el AccountId del dueño actual por el nuevo ingresado por parametro.
+        /// Retorna la cuenta a la que se le propuso el club, si hay alguna.
         #[ink(message)]
-        pub fn cambiar_dueño(&mut self, nuevo_dueño: AccountId) {
-            assert!(!self.politica_autorizacion || self.soy_el_dueño(), "No autorizado (no es dueño)");
-            self.dueño = nuevo_dueño;
+        pub fn get_dueño_propuesto(&self) -> Option<AccountId> {
+            self.dueño_propuesto
         }
         
         /// Retorna el AccountId del dueño actual.
@@ -97,33 +552,48 @@ pub mod trabajo_final {
             self.dueño
         }
 
-        /// Agrega el AccountId ingresado como parametro al listado de autorizados.
+        /// Le otorga el [Rol] dado a la cuenta ingresada.
         #[ink(message)]
-        pub fn agregar_autorizado(&mut self, quien: AccountId) {
-            assert!(!self.politica_autorizacion || self.soy_el_dueño(), "No autorizado (no es dueño)");
-            assert!(!self.autorizados.contains(&quien), "Esa cuenta ya está autorizada");
-            self.autorizados.push(quien);
+        pub fn otorgar_rol(&mut self, quien: AccountId, rol: Rol) -> Result<(), ClubError> {
+            self.verificar_dueño()?;
+            if self.roles.contains(&(quien, rol)) {
+                return Err(ClubError::RolYaOtorgado);
+            }
+            self.roles.push((quien, rol));
+            self.env().emit_event(RolOtorgado { cuenta: quien, rol });
+            Ok(())
+        }
+
+        /// Le revoca el [Rol] dado a la cuenta ingresada.
+        #[ink(message)]
+        pub fn revocar_rol(&mut self, quien: AccountId, rol: Rol) -> Result<(), ClubError> {
+            self.verificar_dueño()?;
+            let i = self.roles.iter().position(|&otorgado| otorgado == (quien, rol))
+                .ok_or(ClubError::RolNoOtorgado)?;
+            self.roles.swap_remove(i);
+            self.env().emit_event(RolRevocado { cuenta: quien, rol });
+            Ok(())
         }
 
-        /// Elimina el AccountId ingresado como parametro al listado de autorizados.
+        /// Retorna un [Vec] con los roles otorgados a la cuenta dada.
         #[ink(message)]
-        pub fn quitar_autorizado(&mut self, quien: AccountId) {
-            assert!(!self.politica_autorizacion || self.soy_el_dueño(), "No autorizado (no es dueño)");
-            let i = self.autorizados.iter().position(|&cuenta| cuenta == quien);
-            self.autorizados.swap_remove(i.expect("No se encuentra la cuenta autorizada."));
+        pub fn get_roles(&self, cuenta: AccountId) -> Vec<Rol> {
+            self.roles.iter().filter(|(c, _)| *c == cuenta).map(|&(_, rol)| rol).collect()
         }
 
-        /// Retorna un vector [Vec] con los AccountId autorizados para operar el contrato.
+        /// Retorna un [Vec] con las cuentas que tienen el rol dado.
         #[ink(message)]
-        pub fn get_autorizados(&self) -> Vec<AccountId> {
-            self.autorizados.clone()
+        pub fn get_cuentas_con_rol(&self, rol: Rol) -> Vec<AccountId> {
+            self.roles.iter().filter(|(_, r)| *r == rol).map(|&(cuenta, _)| cuenta).collect()
         }
 
         /// Cambia el nombre del Club por el nombre ingresado por parametro.
         #[ink(message)]
-        pub fn cambiar_nombre(&mut self, nuevo_nombre: String) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            self.nombre = nuevo_nombre;
+        pub fn cambiar_nombre(&mut self, nuevo_nombre: String) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            self.nombre = nuevo_nombre.clone();
+            self.env().emit_event(NombreCambiado { nombre: nuevo_nombre, cuenta: self.env().caller() });
+            Ok(())
         }
 
         /// Retorna el nombre actual del Club.
@@ -132,31 +602,279 @@ pub mod trabajo_final {
             self.nombre.clone()
         }
 
-        /// Establece el valor del precio de la categoria dada.
+        /// Establece el valor del precio de la categoria dada desde hoy.
+        /// Se usa en los pagos que se generen de ahora en más con vencimiento desde hoy.
+        /// 
+        /// Retorna [ClubError::CategoriaInexistente] si no hay ninguna categoría con esa id.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_precio(&mut self, categoria: IdCategoria, nuevo_valor: u128) -> Result<(), ClubError> {
+            self._set_precio(categoria, nuevo_valor)
+        }
+        pub fn _set_precio(&mut self, categoria: IdCategoria, nuevo_valor: u128) -> Result<(), ClubError> {
+            let hoy = self.obtener_fecha_actual();
+            self._programar_precio(categoria, hoy, nuevo_valor)
+        }
+
+        /// Programa el precio de la categoría dada desde la fecha `desde`, que no puede ser anterior a hoy.
+        /// Los pagos se generan con el precio vigente en su vencimiento, así que el nuevo precio se usa en los pagos
+        /// que venzan desde esa fecha, aunque se generen antes. Si ya había un precio programado para esa fecha, se reemplaza.
+        /// Si hay una [Indexacion], los ajustes siguientes se aplican sobre este precio.
+        /// 
+        /// Retorna [ClubError::CategoriaInexistente] si no hay ninguna categoría con esa id,
+        /// o [ClubError::ValorInvalido] si la fecha ya pasó.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn programar_precio(&mut self, categoria: IdCategoria, desde: Fecha, nuevo_valor: u128) -> Result<(), ClubError> {
+            self._programar_precio(categoria, desde, nuevo_valor)
+        }
+        fn _programar_precio(&mut self, categoria: IdCategoria, desde: Fecha, nuevo_valor: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if self.obtener_fecha_actual().es_mayor(&desde) {
+                return Err(ClubError::ValorInvalido);
+            }
+            let datos = self.categorias.get_mut(categoria as usize).ok_or(ClubError::CategoriaInexistente)?;
+            let precio_anterior = datos.get_precio_en(desde, self.indexacion.as_ref()).ok_or(ClubError::Desbordamiento)?;
+            datos.programar_precio(desde, nuevo_valor);
+            self.env().emit_event(PrecioCambiado {
+                categoria,
+                desde,
+                precio_anterior,
+                precio_nuevo: nuevo_valor,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Retorna el precio vigente hoy de la categoria dada, o [ClubError::CategoriaInexistente] si no existe.
+        #[ink(message)]
+        pub fn get_precio(&self, categoria: IdCategoria) -> Result<u128, ClubError> {
+            self.get_precio_en(categoria, self.obtener_fecha_actual())
+        }
+
+        /// Retorna el precio de la categoria dada vigente en la fecha dada, ya sea pasada o futura,
+        /// con los ajustes de la indexación actual.
+        /// 
+        /// Retorna [ClubError::CategoriaInexistente] si no existe la categoría.
+        #[ink(message)]
+        pub fn get_precio_en(&self, categoria: IdCategoria, fecha: Fecha) -> Result<u128, ClubError> {
+            self.get_categoria(categoria)?.get_precio_en(fecha, self.indexacion.as_ref()).ok_or(ClubError::Desbordamiento)
+        }
+
+        /// Retorna todos los precios que tuvo la categoría dada hasta hoy, contando los ajustes de la indexación,
+        /// y los que tiene programados, con la fecha desde la que rige cada uno, ordenados por fecha.
+        /// 
+        /// Retorna [ClubError::CategoriaInexistente] si no existe la categoría.
+        #[ink(message)]
+        pub fn get_historial_precios(&self, categoria: IdCategoria) -> Result<Vec<(Fecha, u128)>, ClubError> {
+            self.get_categoria(categoria)?
+                .get_historial_precios(self.obtener_fecha_actual(), self.indexacion.as_ref())
+                .ok_or(ClubError::Desbordamiento)
+        }
+
+        /// Establece la indexación automática de los precios de todas las categorías (ver [Indexacion]),
+        /// que empieza a regir en la fecha `desde`, que no puede ser anterior a hoy.
+        /// Los ajustes se calculan al generar cada pago, así que no hace falta ninguna transacción periódica.
+        /// Los ajustes de la indexación anterior que ya rigieron quedan en el historial de precios.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si la fecha ya pasó o si alguno de los otros valores es 0.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_indexacion(&mut self, por_mil: u16, cada_meses: u8, desde: Fecha, multiplo: u128, redondeo: Redondeo) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            let indexacion = Indexacion { por_mil, cada_meses, desde, multiplo, redondeo };
+            indexacion.assert_valida()?;
+            if self.obtener_fecha_actual().es_mayor(&desde) {
+                return Err(ClubError::ValorInvalido);
+            }
+            self.cambiar_indexacion(Some(indexacion))
+        }
+
+        /// Quita la indexación automática de los precios. Los ajustes que ya rigieron quedan en el historial de precios,
+        /// y el último sigue siendo el precio de cada categoría.
+        /// 
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn quitar_indexacion(&mut self) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            self.cambiar_indexacion(None)
+        }
+
+        /// Retorna la indexación automática de los precios, si hay.
+        #[ink(message)]
+        pub fn get_indexacion(&self) -> Option<Indexacion> {
+            self.indexacion
+        }
+
+        fn cambiar_indexacion(&mut self, indexacion: Option<Indexacion>) -> Result<(), ClubError> {
+            // La indexación nueva rige desde hoy, así que se fijan los ajustes de la anterior hasta ayer
+            let mut ayer = self.obtener_fecha_actual();
+            ayer.restar_dias(1);
+            let mut categorias = self.categorias.clone();
+            for categoria in categorias.iter_mut() {
+                categoria.fijar_ajustes(ayer, self.indexacion.as_ref()).ok_or(ClubError::Desbordamiento)?;
+            }
+            self.categorias = categorias;
+            self.indexacion = indexacion;
+            self.env().emit_event(IndexacionCambiada {
+                indexacion,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Registra una nueva actividad y retorna su id.
+        /// 
+        /// Retorna [ClubError::NombreRepetido] si ya hay una actividad con ese nombre.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn registrar_actividad(&mut self, nombre: String) -> Result<IdActividad, ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            self._registrar_actividad(nombre)
+        }
+        fn _registrar_actividad(&mut self, nombre: String) -> Result<IdActividad, ClubError> {
+            if self.actividades.contains(&nombre) {
+                return Err(ClubError::NombreRepetido);
+            }
+            let id = self.actividades.len() as IdActividad;
+            self.actividades.push(nombre.clone());
+            self.env().emit_event(ActividadRegistrada { id, nombre, cuenta: self.env().caller() });
+            Ok(id)
+        }
+
+        /// Retorna los nombres de todas las actividades. La id de cada actividad es su posición.
+        #[ink(message)]
+        pub fn get_actividades(&self) -> Vec<String> {
+            self.actividades.clone()
+        }
+
+        /// Registra una nueva categoría y retorna su id.
+        /// 
+        /// Los socios de la categoría pueden acceder a todas las actividades `incluidas`, y a `cantidad_a_elegir` actividades
+        /// que eligen entre las `elegibles`. Para permitir elegir actividades extra está [Club::set_actividades_extra].
+        /// 
+        /// Retorna [ClubError::NombreRepetido] si ya hay una categoría con ese nombre, [ClubError::ActividadInexistente]
+        /// si alguna actividad no existe, o [ClubError::ValorInvalido] si hay menos actividades elegibles que las que hay que elegir.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn registrar_categoria(
+            &mut self,
+            nombre: String,
+            precio: u128,
+            incluidas: Vec<IdActividad>,
+            elegibles: Vec<IdActividad>,
+            cantidad_a_elegir: u8,
+        ) -> Result<IdCategoria, ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            let hoy = self.obtener_fecha_actual();
+            self._registrar_categoria(Categoria::new(nombre, precio, incluidas, elegibles, cantidad_a_elegir, hoy))
+        }
+        fn _registrar_categoria(&mut self, categoria: Categoria) -> Result<IdCategoria, ClubError> {
+            if self.categorias.iter().any(|c| c.nombre == categoria.nombre) {
+                return Err(ClubError::NombreRepetido);
+            }
+            self.validar_actividades_categoria(&categoria)?;
+            let id = self.categorias.len() as IdCategoria;
+            let nombre = categoria.nombre.clone();
+            self.categorias.push(categoria);
+            self.env().emit_event(CategoriaRegistrada { id, nombre, cuenta: self.env().caller() });
+            Ok(id)
+        }
+
+        /// Cambia las actividades de la categoría dada (ver [Club::registrar_categoria]).
+        /// Las actividades que ya eligieron los socios de la categoría y dejan de ser elegibles ya no les dan acceso.
+        /// 
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn modificar_actividades_categoria(
+            &mut self,
+            id: IdCategoria,
+            incluidas: Vec<IdActividad>,
+            elegibles: Vec<IdActividad>,
+            cantidad_a_elegir: u8,
+        ) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            let mut categoria = self.get_categoria(id)?;
+            categoria.incluidas = incluidas;
+            categoria.elegibles = elegibles;
+            categoria.cantidad_a_elegir = cantidad_a_elegir;
+            categoria.maximo_a_elegir = categoria.maximo_a_elegir.max(cantidad_a_elegir);
+            self.validar_actividades_categoria(&categoria)?;
+            self.categorias[id as usize] = categoria;
+            self.env().emit_event(CategoriaModificada { id, cuenta: self.env().caller() });
+            Ok(())
+        }
+
+        /// Permite que los socios de la categoría dada elijan actividades extra, hasta `maximo_a_elegir` en total,
+        /// pagando `precio_por_actividad_extra` por cada una además del precio de la categoría.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el máximo es menor a la cantidad de actividades a elegir de la categoría.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_actividades_extra(&mut self, id: IdCategoria, maximo_a_elegir: u8, precio_por_actividad_extra: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            let categoria = self.categorias.get_mut(id as usize).ok_or(ClubError::CategoriaInexistente)?;
+            if maximo_a_elegir < categoria.cantidad_a_elegir {
+                return Err(ClubError::ValorInvalido);
+            }
+            categoria.maximo_a_elegir = maximo_a_elegir;
+            categoria.precio_por_actividad_extra = precio_por_actividad_extra;
+            self.env().emit_event(CategoriaModificada { id, cuenta: self.env().caller() });
+            Ok(())
+        }
+
+        /// Verifica que existan todas las actividades de la categoría, y que alcancen las elegibles.
+        fn validar_actividades_categoria(&self, categoria: &Categoria) -> Result<(), ClubError> {
+            let cantidad_actividades = self.actividades.len() as IdActividad;
+            if categoria.incluidas.iter().chain(categoria.elegibles.iter()).any(|&id| id >= cantidad_actividades) {
+                return Err(ClubError::ActividadInexistente);
+            }
+            if categoria.elegibles.len() < categoria.cantidad_a_elegir as usize {
+                return Err(ClubError::ValorInvalido);
+            }
+            Ok(())
+        }
+
+        /// Retorna la categoría con la id dada, o [ClubError::CategoriaInexistente] si no existe.
         #[ink(message)]
-        pub fn set_precio(&mut self, categoria: Categoria, nuevo_valor: u128) {
-            self._set_precio(categoria, nuevo_valor);
+        pub fn get_categoria(&self, id: IdCategoria) -> Result<Categoria, ClubError> {
+            self.categorias.get(id as usize).cloned().ok_or(ClubError::CategoriaInexistente)
         }
-        pub fn _set_precio(&mut self, categoria: Categoria, nuevo_valor: u128) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            self.precios[categoria.num()] = nuevo_valor;
+
+        /// Retorna todas las categorías. La id de cada categoría es su posición.
+        #[ink(message)]
+        pub fn get_categorias(&self) -> Vec<Categoria> {
+            self.categorias.clone()
         }
 
-        /// Retorna el precio de la categoria dada.
+        /// Retorna true si el socio activo con el dni dado puede acceder a la actividad dada según su categoría.
         #[ink(message)]
-        pub fn get_precio(&self, categoria: Categoria) -> u128 {
-            self._get_precio(categoria)
+        pub fn puede_acceder_a(&self, dni: u128, actividad: IdActividad) -> Result<bool, ClubError> {
+            let (_, socio) = self.socio_por_dni(dni)?;
+            Ok(socio.es_activo() && self.categorias[socio.categoria as usize].puede_acceder_a(&socio.actividades_elegidas, actividad))
+        }
+
+        /// Verifica que la categoría exista y que las actividades elegidas sean válidas para ella.
+        fn validar_eleccion(&self, categoria: IdCategoria, elegidas: &[IdActividad]) -> Result<(), ClubError> {
+            self.get_categoria(categoria)?.validar_eleccion(elegidas)
         }
-        fn _get_precio(&self, categoria: Categoria) -> u128 {
-            self.precios[categoria.num()]
+
+        /// Retorna lo que paga por un período con el vencimiento dado un socio de la categoría dada (que ya se sabe que existe)
+        /// con las actividades elegidas.
+        fn precio_socio(&self, categoria: IdCategoria, elegidas: &[IdActividad], vencimiento: Fecha) -> Result<u128, ClubError> {
+            self.categorias[categoria as usize].precio_con(elegidas, vencimiento, self.indexacion.as_ref()).ok_or(ClubError::Desbordamiento)
         }
 
         /// Establece la cantidad de pagos consecutivos necesarios para acceder a la bonificación de precio. 
         #[ink(message)]
-        pub fn set_cantidad_pagos_bonificacion(&mut self, nuevo_valor:u16) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            assert!(nuevo_valor > 0);
+        pub fn set_cantidad_pagos_bonificacion(&mut self, nuevo_valor:u16) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if nuevo_valor == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
             self.cantidad_pagos_bonificacion = nuevo_valor;
+            self.emitir_bonificacion_cambiada();
+            Ok(())
         }
 
         /// Retorna la cantidad de pagos consecutivos necesarios para acceder a la bonificación de precio.
@@ -167,10 +885,22 @@ pub mod trabajo_final {
 
         /// Establece el porcentaje de bonificación de descuento por pagos consecutivos.
         #[ink(message)]
-        pub fn set_porcentaje_bonificacion_pagos_consecutivos(&mut self, nuevo_valor: u8) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            assert!(nuevo_valor > 0 && nuevo_valor < 100);
+        pub fn set_porcentaje_bonificacion_pagos_consecutivos(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if nuevo_valor == 0 || nuevo_valor >= 100 {
+                return Err(ClubError::ValorInvalido);
+            }
             self.porcentaje_bonificacion = nuevo_valor;
+            self.emitir_bonificacion_cambiada();
+            Ok(())
+        }
+
+        fn emitir_bonificacion_cambiada(&self) {
+            self.env().emit_event(BonificacionCambiada {
+                cantidad_pagos_bonificacion: self.cantidad_pagos_bonificacion,
+                porcentaje_bonificacion: self.porcentaje_bonificacion,
+                cuenta: self.env().caller(),
+            });
         }
 
         /// Retorna el porcentaje de bonificación de descuento por pagos consecutivos.
@@ -180,138 +910,1425 @@ pub mod trabajo_final {
         }
 
         /// Retorna un [Vec] con todos los [Socio]s registrados.
+        /// 
+        /// Requiere el rol [Rol::Auditor].
         #[ink(message)]
-        pub fn get_socios(&self) -> Vec<Socio> {
-            self.socios.clone()
+        pub fn get_socios(&self) -> Result<Vec<Socio>, ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
+            Ok((0..self.cantidad_socios).filter_map(|id| self.socios.get(id)).collect())
         }
         
         /// Obtiene en un [Vec] todos los pagos de todos los socios o el socio con dni especificado en el Option.
+        /// 
+        /// Retorna [ClubError::SocioInexistente] si no hay ningún socio con ese dni.
+        /// Requiere el rol [Rol::Auditor].
         #[ink(message)]
-        pub fn get_pagos(&self, socio: Option<u128>)-> Vec<Pago>{
+        pub fn get_pagos(&self, socio: Option<u128>)-> Result<Vec<Pago>, ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
             if let Some(dni) = socio {
-                let id = self.buscar_socio(dni).expect("No existe socio con dni {dni}") as u64;
-                self.pagos.iter().filter(|&p| p.id_socio == id).map(|p| p.clone()).collect()
+                let id_socio = self.buscar_socio(dni).ok_or(ClubError::SocioInexistente)?;
+                let cantidad = self.cantidad_pagos_por_socio.get(id_socio).unwrap_or(0);
+                Ok((0..cantidad)
+                    .filter_map(|n| self.pagos_por_socio.get((id_socio, n)))
+                    .filter_map(|id_pago| self.pagos.get(id_pago))
+                    .collect())
+            } else {
+                Ok((0..self.cantidad_pagos).filter_map(|id| self.pagos.get(id)).collect())
+            }
+        }
+
+        /// Retorna una página de los socios que cumplen el filtro dado: recorre `cantidad` socios en orden de id, empezando
+        /// por el id `desde`, y retorna los que cumplen el filtro junto con el id desde el que sigue la página siguiente,
+        /// o None si no hay más. Como se recorren `cantidad` socios aunque no cumplan el filtro, una página puede tener
+        /// menos socios (o ninguno) aunque haya más.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si `cantidad` es 0.
+        /// Requiere el rol [Rol::Auditor].
+        #[ink(message)]
+        pub fn get_socios_pagina(&self, desde: u64, cantidad: u64, filtro: FiltroSocios) -> Result<(Vec<Socio>, Option<u64>), ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
+            let (ids, siguiente) = Self::rango_pagina(desde, cantidad, self.cantidad_socios)?;
+            let socios = ids
+                .filter_map(|id| self.socios.get(id))
+                .filter(|socio| filtro.acepta(socio, &self.categorias[socio.categoria as usize]))
+                .collect();
+            Ok((socios, siguiente))
+        }
+
+        /// Retorna una página de los pagos que cumplen el filtro dado, como [Club::get_socios_pagina]: recorre `cantidad` pagos
+        /// empezando por `desde`, y retorna los que cumplen el filtro junto con la posición desde la que sigue la página
+        /// siguiente, o None si no hay más. Si se filtra por socio, se recorren sólo sus pagos y las posiciones son las de
+        /// sus pagos, en orden. Si no, se recorren todos los pagos y las posiciones son sus ids.
+        /// 
+        /// Retorna [ClubError::SocioInexistente] si se filtra por un dni que no es de ningún socio,
+        /// o [ClubError::ValorInvalido] si `cantidad` es 0.
+        /// Requiere el rol [Rol::Auditor].
+        #[ink(message)]
+        pub fn get_pagos_pagina(&self, desde: u64, cantidad: u64, filtro: FiltroPagos) -> Result<(Vec<Pago>, Option<u64>), ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
+            let pagos: Vec<Pago>;
+            let siguiente;
+            if let Some(dni) = filtro.socio {
+                let id_socio = self.buscar_socio(dni).ok_or(ClubError::SocioInexistente)?;
+                let total = self.cantidad_pagos_por_socio.get(id_socio).unwrap_or(0) as u64;
+                let (posiciones, siguiente_posicion) = Self::rango_pagina(desde, cantidad, total)?;
+                pagos = posiciones
+                    .filter_map(|n| self.pagos_por_socio.get((id_socio, n as u32)))
+                    .filter_map(|id_pago| self.pagos.get(id_pago))
+                    .filter(|pago| filtro.acepta(pago))
+                    .collect();
+                siguiente = siguiente_posicion;
             } else {
-                self.pagos.clone()
+                let (ids, siguiente_id) = Self::rango_pagina(desde, cantidad, self.cantidad_pagos)?;
+                pagos = ids.filter_map(|id| self.pagos.get(id)).filter(|pago| filtro.acepta(pago)).collect();
+                siguiente = siguiente_id;
+            }
+            Ok((pagos, siguiente))
+        }
+
+        /// Retorna las posiciones de una página de `cantidad` elementos desde `desde`, de un total de `total`,
+        /// y la posición desde la que sigue la página siguiente, o None si no hay más.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si `cantidad` es 0.
+        fn rango_pagina(desde: u64, cantidad: u64, total: u64) -> Result<(core::ops::Range<u64>, Option<u64>), ClubError> {
+            if cantidad == 0 {
+                return Err(ClubError::ValorInvalido);
             }
+            let hasta = desde.saturating_add(cantidad).min(total);
+            Ok((desde..hasta, (hasta < total).then_some(hasta)))
         }
 
-        /// Registra un nuevo socio y genera el proximo pago con vencimiento en los proximos 10 dias.
+        /// Registra un nuevo socio y genera el proximo pago con vencimiento en los proximos dias
+        /// (ver [Club::get_dias_primer_vencimiento]).
+        #[ink(message)]
+        pub fn registrar_nuevo_socio(&mut self, dni: u128, nombre: String, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {
+            self._registrar_nuevo_socio(SocioAImportar { dni, nombre, categoria, actividades_elegidas, alta: None, deuda: None }, None)
+        }
+
+        /// Registra un nuevo socio como [Club::registrar_nuevo_socio], usando el cupón con el código dado en su primer pago.
+        /// 
+        /// Retorna [ClubError::CuponInexistente] o [ClubError::CuponNoAplicable] si no se puede usar el cupón,
+        /// y en ese caso el socio no se registra.
+        #[ink(message)]
+        pub fn registrar_nuevo_socio_con_cupon(&mut self, dni: u128, nombre: String, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>, codigo: String) -> Result<(), ClubError> {
+            let fila = SocioAImportar { dni, nombre, categoria, actividades_elegidas, alta: None, deuda: None };
+            self._registrar_nuevo_socio(fila, Some(codigo))
+        }
+
+        /// Registra de una vez a los socios dados, por ejemplo al migrar el padrón de socios de otro sistema.
+        /// Cada uno se registra como con [Club::registrar_nuevo_socio], pero puede tener una fecha de alta anterior a hoy
+        /// y una deuda del sistema anterior, que se suma a su primer pago.
+        /// 
+        /// Cada fila se valida antes de registrarla, y si no se puede registrar no impide registrar las demás:
+        /// retorna el resultado de cada fila en el mismo orden, con el error por el que no se registró.
+        /// La fecha de alta no puede ser posterior a hoy (si no, la fila falla con [ClubError::ValorInvalido]).
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si no hay socios o si son más de [MAXIMO_SOCIOS_POR_IMPORTACION],
+        /// para no pasarse de los límites de gas y tamaño (las listas más largas se importan en varias llamadas).
+        /// Requiere el rol [Rol::Secretaria].
         #[ink(message)]
-        pub fn registrar_nuevo_socio(&mut self, dni: u128, nombre: String, categoria:Categoria) {
-            self._registrar_nuevo_socio(dni, nombre, categoria);
+        pub fn importar_socios(&mut self, socios: Vec<SocioAImportar>) -> Result<Vec<Result<(), ClubError>>, ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            if socios.is_empty() || socios.len() > MAXIMO_SOCIOS_POR_IMPORTACION {
+                return Err(ClubError::ValorInvalido);
+            }
+            Ok(socios.into_iter().map(|fila| self._registrar_nuevo_socio(fila, None)).collect())
         }
-        fn _registrar_nuevo_socio(&mut self, dni: u128, nombre: String, categoria: Categoria) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            match self.buscar_socio(dni) {
-                Some(idx) => panic!("Ya existe un socio con el dni {dni}: ({:?})", self.socios[idx]),
-                None => (),
+
+        fn _registrar_nuevo_socio(&mut self, fila: SocioAImportar, codigo_cupon: Option<String>) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let SocioAImportar { dni, nombre, categoria, actividades_elegidas, alta, deuda } = fila;
+            if self.buscar_socio(dni).is_some() {
+                return Err(ClubError::SocioYaRegistrado);
+            }
+            self.validar_eleccion(categoria, &actividades_elegidas)?;
+            let fecha_actual = self.obtener_fecha_actual();
+            if alta.is_some_and(|alta| alta.es_mayor(&fecha_actual)) {
+                return Err(ClubError::ValorInvalido);
             }
-            categoria.assert_valida();
-            let mut valor_pago = self.get_precio(categoria);
+            let cupon = codigo_cupon.map(|codigo| self.cupon_aplicable(&codigo, categoria)).transpose()?;
             
             let mut socio = Socio {
                 dni,
                 nombre,
                 categoria,
+                actividades_elegidas,
                 pagos_a_tiempo_consecutivos: 0,
+                cuenta: None,
+                estado: EstadoSocio::Activo,
+                dia_de_vencimiento: 1,
+                titular: None,
+                dependientes: Vec::new(),
+                primer_pago: 0,
+                saldo_a_favor: 0,
+                fecha_alta: alta.unwrap_or(fecha_actual),
             };
+            let id_socio = self.cantidad_socios;
+
+            self.generar_primer_pago(id_socio, &mut socio, deuda.unwrap_or(0))?;
+            self.socios.insert(id_socio, &socio);
+            self.id_por_dni.insert(dni, &id_socio);
+            self.cantidad_socios += 1;
+            self.env().emit_event(SocioRegistrado { dni, categoria, cuenta: self.env().caller() });
+            if let Some(cupon) = cupon {
+                self.usar_cupon(dni, id_socio, cupon);
+            }
+            Ok(())
+        }
 
-            let mut vencimiento: Fecha = self.obtener_fecha_actual();
-            vencimiento.sumar_dias(10);
-            let pago_final: Pago = Pago {
-                id_socio: self.pagos.len() as u64,
-                monto: self.get_precio(categoria),
+        /// Genera el primer pago de un socio que se registra o se readmite, con vencimiento en los próximos
+        /// [Club::get_dias_primer_vencimiento] días. Los siguientes pagos vencen el mismo día del mes que este,
+        /// si el ciclo es por meses, así que se guarda en el socio (que no se guarda acá), al igual que el saldo a favor que se usa
+        /// y la posición del pago en su historial, desde la que empiezan los pagos que se pueden revertir.
+        /// La deuda anterior dada se suma al monto del pago.
+        fn generar_primer_pago(&mut self, id_socio: u64, socio: &mut Socio, deuda_anterior: u128) -> Result<(), ClubError> {
+            let categoria = socio.categoria;
+            let vencimiento = self.primer_vencimiento();
+            let monto = self.precio_facturado(socio, vencimiento)?.checked_add(deuda_anterior).ok_or(ClubError::Desbordamiento)?;
+            socio.dia_de_vencimiento = vencimiento.get_dia();
+            let mut pago_final: Pago = Pago {
+                id_socio,
+                monto,
                 pagado: None,
                 vencimiento,
-                es_descuento: false
+                es_descuento: false,
+                cancelado: false,
+                es_adelantado: false,
+                pagos_parciales: Vec::new(),
+                recargo: 0,
+                categoria,
+                categoria_anterior: None,
+                cupon: None,
+                descuento_cupon: 0,
+                pagos_con_tokens: Vec::new(),
+                pagos_a_tiempo_previos: 0,
+                revertido: None,
+                saldo_aplicado: 0,
             };
-            self.pagos.push(pago_final);
-            self.socios.push(socio);
+            Self::aplicar_saldo_a_favor(socio, &mut pago_final);
+            socio.primer_pago = self.cantidad_pagos_por_socio.get(id_socio).unwrap_or(0);
+            self.agregar_pago(&pago_final);
+            Ok(())
         }
 
-        /// Busca un socio y retorna un Option con su id en caso de existir en el registro, caso contrario
-        /// retorna None.
-        fn buscar_socio(&self, dni: u128) -> Option<usize> {
-            for (idx, socio) in self.socios.iter().enumerate() {
-                if socio.dni == dni {
-                    return Some(idx);
-                }
+        /// Suspende al socio con el dni dado. Mientras está suspendido no puede pagar ni acceder a las actividades,
+        /// y no se lo considera moroso.
+        /// 
+        /// Retorna [ClubError::EstadoInvalido] si el socio no está activo.
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn suspender_socio(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if socio.estado != EstadoSocio::Activo {
+                return Err(ClubError::EstadoInvalido);
             }
-            None
+            socio.estado = EstadoSocio::Suspendido;
+            self.guardar_estado(id_socio, &socio);
+            Ok(())
         }
 
-        /// Devuelve el socio con la id dada.
+        /// Vuelve a activar al socio suspendido con el dni dado.
+        /// Si su pago pendiente venció durante la suspensión, se le dan otros [Club::get_dias_primer_vencimiento] días para pagarlo.
+        /// Si es dependiente de un grupo familiar no tiene pago propio, así que sólo vuelve a contarse en los pagos del grupo.
+        /// 
+        /// Retorna [ClubError::EstadoInvalido] si el socio no está suspendido.
+        /// Requiere el rol [Rol::Secretaria].
         #[ink(message)]
-        pub fn get_socio(&self, id: u64) -> Option<Socio> {
-            self.socios.get(id as usize).cloned()
+        pub fn reactivar_socio(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if socio.estado != EstadoSocio::Suspendido {
+                return Err(ClubError::EstadoInvalido);
+            }
+            if socio.titular.is_none() {
+                let id_pago = self.buscar_ultimo_pago(id_socio);
+                let mut pago = self.pagos.get(id_pago).expect("El pago pendiente debe existir");
+                let fecha_actual = self.obtener_fecha_actual();
+                if pago.es_moroso(fecha_actual) {
+                    pago.vencimiento = self.primer_vencimiento();
+                    self.pagos.insert(id_pago, &pago);
+                }
+            }
+            socio.estado = EstadoSocio::Activo;
+            self.guardar_estado(id_socio, &socio);
+            Ok(())
         }
 
-        /// Obtiene id del último pago pendiente del socio dado.
-        fn buscar_ultimo_pago(&self, id_socio: u64) -> usize {
-            // rev() para buscar el último
-            for (i, pago) in self.pagos.iter().enumerate().rev() {
-                if pago.id_socio == id_socio {
-                    assert!(pago.pagado.is_none(), "Todo socio debe tener registrado el siguiente pago pendiente");
-                    return i
+        /// Da de baja al socio con el dni dado, que puede estar activo o suspendido. Su pago pendiente y los facturados se cancelan
+        /// y no se le generan más pagos. El socio, su saldo a favor y sus pagos anteriores se conservan.
+        /// Si es dependiente de un grupo familiar, deja el grupo.
+        /// 
+        /// Retorna [ClubError::EstadoInvalido] si el socio ya estaba dado de baja, o [ClubError::GrupoFamiliarInvalido]
+        /// si es titular de un grupo familiar con dependientes (primero hay que quitarlos).
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn dar_de_baja_socio(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if socio.estado == EstadoSocio::Baja {
+                return Err(ClubError::EstadoInvalido);
+            }
+            if !socio.dependientes.is_empty() {
+                return Err(ClubError::GrupoFamiliarInvalido);
+            }
+            if socio.titular.is_some() {
+                self.quitar_del_grupo(id_socio, &mut socio);
+            } else {
+                // El saldo a favor que se había usado en los pagos cancelados vuelve al socio
+                for pago in self.cancelar_pagos_impagos(id_socio) {
+                    socio.saldo_a_favor = socio.saldo_a_favor.saturating_add(pago.saldo_aplicado);
                 }
             }
-            panic!("Id de socio inválido")
+            socio.estado = EstadoSocio::Baja;
+            socio.pagos_a_tiempo_consecutivos = 0;
+            self.guardar_estado(id_socio, &socio);
+            Ok(())
         }
 
-        /// Se registra el pago del dni ingresado solo si el monto ingresado es igual al monto a pagar
-        /// según su pago pendiente.
+        /// Readmite al socio dado de baja con el dni dado, en la categoría y con las actividades elegidas.
+        /// Conserva su historial de pagos y se le genera un nuevo pago como al registrarse.
         /// 
-        /// Una vez registrado el pago actual se genera automaticamente el siguiente pago del usuario con su respectivo vencimiento
-        /// y bonificación.
+        /// Retorna [ClubError::EstadoInvalido] si el socio no estaba dado de baja.
+        /// Requiere el rol [Rol::Secretaria].
         #[ink(message)]
-        pub fn realizar_pago(&mut self, dni: u128, monto: u128) {
-            self._realizar_pago(dni, monto)
+        pub fn readmitir_socio(&mut self, dni: u128, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if socio.estado != EstadoSocio::Baja {
+                return Err(ClubError::EstadoInvalido);
+            }
+            self.validar_eleccion(categoria, &actividades_elegidas)?;
+            socio.categoria = categoria;
+            socio.actividades_elegidas = actividades_elegidas;
+            socio.estado = EstadoSocio::Activo;
+            self.generar_primer_pago(id_socio, &mut socio, 0)?;
+            self.guardar_estado(id_socio, &socio);
+            Ok(())
         }
-        fn _realizar_pago(&mut self, dni: u128, monto: u128) {
-            assert!(self.estoy_autorizado(), "No autorizado");
-            let id_socio = match self.buscar_socio(dni) {
-                None => {
-                    panic!("No existe ningún socio con el dni {dni}")
-                },
-                Some(id) => id
-            };
-            let id_pago = self.buscar_ultimo_pago(id_socio as u64);
-            let fecha_actual = self.obtener_fecha_actual();
-            
-            let pago = self.pagos.get_mut(id_pago).unwrap();
-            assert_eq!(pago.monto, monto, "El monto a pagar es {}", pago.monto);
-            assert!(!pago.es_pagado());
-            pago.pagado = Some (fecha_actual);
-            // los pagos con descuento no cuentan para el siguiente descuento
-            if pago.es_pagado_a_tiempo().unwrap() && !pago.es_descuento {
-                self.socios[id_socio].pagos_a_tiempo_consecutivos += 1;
+
+        /// Cambia la categoría del socio activo con el dni dado, o las actividades que eligió.
+        /// 
+        /// El pago del período actual (el pendiente, o el último facturado si la facturación ya generó pagos después del pendiente,
+        /// ver [Club::facturar]) se recalcula en proporción a los días que faltan para su vencimiento (cada pago cubre
+        /// el ciclo de facturación anterior a su vencimiento): esos días se cobran con el precio de la nueva categoría, con la
+        /// bonificación aplicada si el pago era con descuento, y el resto con el monto que ya tenía antes de descontarle el cupón
+        /// y el saldo a favor. Después se vuelven a descontar, y el saldo a favor que ya no se usa vuelve al socio.
+        /// El pago guarda la categoría anterior, y los siguientes se generan con la nueva. Si ya venció, no cambia.
+        /// Si el socio es titular de un grupo familiar, se usa el precio de todo el grupo. Si es dependiente, el pago del período
+        /// es el del grupo y no se recalcula: el cambio se cobra desde el siguiente pago del grupo.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el socio ya está en esa categoría con esas mismas actividades.
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn cambiar_categoria(&mut self, dni: u128, categoria: IdCategoria, actividades_elegidas: Vec<IdActividad>) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if !socio.es_activo() {
+                return Err(ClubError::SocioNoActivo);
+            }
+            self.validar_eleccion(categoria, &actividades_elegidas)?;
+            if socio.categoria == categoria && socio.actividades_elegidas == actividades_elegidas {
+                return Err(ClubError::ValorInvalido);
             }
+            let categoria_anterior = socio.categoria;
+            socio.categoria = categoria;
+            socio.actividades_elegidas = actividades_elegidas;
+            // Los pagos facturados son de los períodos posteriores al del pendiente
+            let id_facturado = socio.titular.unwrap_or(id_socio);
+            let id_pago = match self.pagos_facturados.get(id_facturado).unwrap_or_default().last() {
+                Some(&id_pago) => id_pago,
+                None => self.buscar_ultimo_pago(id_facturado),
+            };
+            let mut pago = self.pagos.get(id_pago).expect("El pago del período actual debe existir");
 
-            // Generar el siguiente pago
-            let mut nuevo_pago = pago.clone();
+            if socio.titular.is_none() {
+                let dias_del_ciclo = self.ciclo_facturacion.dias_antes_de(pago.vencimiento) as u128;
+                let dias_restantes = (self.obtener_fecha_actual().dias_hasta(&pago.vencimiento).max(0) as u128).min(dias_del_ciclo);
 
-            let mut fecha_siguiente = pago.vencimiento.clone();
-            fecha_siguiente.sumar_dias(30);
-            nuevo_pago.pagado = None;
-            nuevo_pago.vencimiento = fecha_siguiente;
-            nuevo_pago.es_descuento = false;
-            let socio = &self.socios[id_socio];
-            nuevo_pago.monto = self.get_precio(socio.categoria);
+                // Si el pago ya venció no tiene días con el precio nuevo, y el cambio se cobra desde el siguiente
+                if dias_restantes > 0 {
+                    let mut precio_nuevo = self.precio_facturado(&socio, pago.vencimiento)?;
+                    if pago.es_descuento {
+                        let procentaje_del_total = (100 - self.porcentaje_bonificacion) as u128;
+                        precio_nuevo = precio_nuevo.checked_mul(procentaje_del_total).ok_or(ClubError::Desbordamiento)? / 100;
+                    }
+                    let monto_anterior = pago.monto.checked_add(pago.saldo_aplicado)
+                        .and_then(|monto| monto.checked_add(pago.descuento_cupon))
+                        .ok_or(ClubError::Desbordamiento)?;
+                    let parte_anterior = monto_anterior.checked_mul(dias_del_ciclo - dias_restantes).ok_or(ClubError::Desbordamiento)? / dias_del_ciclo;
+                    let parte_nueva = precio_nuevo.checked_mul(dias_restantes).ok_or(ClubError::Desbordamiento)? / dias_del_ciclo;
+                    let mut monto = parte_anterior.checked_add(parte_nueva).ok_or(ClubError::Desbordamiento)?;
+                    let descuento_cupon = pago.descuento_cupon.min(monto);
+                    monto -= descuento_cupon;
+                    let saldo_aplicado = pago.saldo_aplicado.min(monto);
+                    monto -= saldo_aplicado;
+                    socio.saldo_a_favor = socio.saldo_a_favor.checked_add(pago.saldo_aplicado - saldo_aplicado).ok_or(ClubError::Desbordamiento)?;
+                    pago.monto = monto.max(pago.get_monto_pagado());
+                    pago.descuento_cupon = descuento_cupon;
+                    pago.saldo_aplicado = saldo_aplicado;
+                    pago.categoria_anterior = Some(pago.categoria);
+                    pago.categoria = categoria;
+                    self.pagos.insert(id_pago, &pago);
+                }
+            }
+            self.socios.insert(id_socio, &socio);
+            self.env().emit_event(CategoriaCambiada {
+                dni,
+                categoria_anterior,
+                categoria_nueva: categoria,
+                monto_pendiente: pago.monto,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
 
-            // Aplicar bonificación
-            if socio.pagos_a_tiempo_consecutivos >= self.cantidad_pagos_bonificacion {
-                let procentaje_del_total = (100 - self.porcentaje_bonificacion) as u128;
-                self.socios[id_socio].pagos_a_tiempo_consecutivos = 0;
-                nuevo_pago.es_descuento = true;
-                assert!(nuevo_pago.monto <= u128::MAX / procentaje_del_total, "valor demasiado grande para aplicar descuento");
-                nuevo_pago.monto = nuevo_pago.monto * procentaje_del_total / 100; 
+        /// Agrega al socio con el dni dado como dependiente del grupo familiar del titular. Desde entonces el titular paga
+        /// un único pago por todo el grupo: lo que falta pagar del pago pendiente y los facturados del dependiente se suma al
+        /// pendiente del titular (y los del dependiente se cancelan), y los siguientes pagos del titular incluyen el precio de cada dependiente
+        /// activo, con el descuento familiar (ver [Club::set_porcentaje_descuento_familiar]).
+        /// El saldo a favor del dependiente pasa al titular, que es quien lo usa en los pagos del grupo.
+        /// Los dependientes se consideran morosos cuando el titular lo es.
+        /// 
+        /// Retorna [ClubError::SocioNoActivo] si alguno de los dos no está activo, o [ClubError::GrupoFamiliarInvalido] si son
+        /// el mismo socio, si el titular es dependiente de otro grupo, si el dependiente ya está en un grupo, o si el pago pendiente
+        /// del dependiente tiene pagos parciales o un cupón (que se perderían al cancelarlo: primero hay que terminar de pagarlo).
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn agregar_dependiente(&mut self, dni_titular: u128, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_titular, mut titular) = self.socio_por_dni(dni_titular)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if !titular.es_activo() || !socio.es_activo() {
+                return Err(ClubError::SocioNoActivo);
+            }
+            if id_titular == id_socio || titular.titular.is_some() || socio.titular.is_some() || !socio.dependientes.is_empty() {
+                return Err(ClubError::GrupoFamiliarInvalido);
             }
+            let pendiente = self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir");
+            if !pendiente.pagos_parciales.is_empty() || !pendiente.pagos_con_tokens.is_empty() || pendiente.cupon.is_some() {
+                return Err(ClubError::GrupoFamiliarInvalido);
+            }
+            let (_, facturados) = self.buscar_pagos_facturados(id_socio);
+            let id_pago_titular = self.buscar_ultimo_pago(id_titular);
+            let mut pago_titular = self.pagos.get(id_pago_titular).expect("El pago pendiente debe existir");
+            for pago in core::iter::once(&pendiente).chain(facturados.iter()) {
+                pago_titular.monto = pago_titular.monto.checked_add(pago.get_monto_restante()).ok_or(ClubError::Desbordamiento)?;
+            }
+            titular.saldo_a_favor = titular.saldo_a_favor.checked_add(socio.saldo_a_favor).ok_or(ClubError::Desbordamiento)?;
 
-            self.pagos.push(nuevo_pago);
+            self.cancelar_pagos_impagos(id_socio);
+            self.pagos.insert(id_pago_titular, &pago_titular);
+            socio.titular = Some(id_titular);
+            socio.pagos_a_tiempo_consecutivos = 0;
+            socio.saldo_a_favor = 0;
+            titular.dependientes.push(id_socio);
+            self.socios.insert(id_socio, &socio);
+            self.socios.insert(id_titular, &titular);
+            self.env().emit_event(DependienteAgregado { dni_titular, dni, cuenta: self.env().caller() });
+            Ok(())
         }
 
-        /// Retorna la fecha actual en un Struct con año, mes y día.
+        /// Quita al socio con el dni dado del grupo familiar en el que es dependiente. Vuelve a pagar por su cuenta,
+        /// con un nuevo pago como al registrarse. El pago pendiente del grupo no cambia, y el saldo a favor del grupo
+        /// queda del titular.
+        /// 
+        /// Retorna [ClubError::GrupoFamiliarInvalido] si el socio no es dependiente de ningún grupo, o [ClubError::SocioNoActivo]
+        /// si no está activo.
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn quitar_dependiente(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let (id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if socio.titular.is_none() {
+                return Err(ClubError::GrupoFamiliarInvalido);
+            }
+            if !socio.es_activo() {
+                return Err(ClubError::SocioNoActivo);
+            }
+            self.generar_primer_pago(id_socio, &mut socio, 0)?;
+            self.quitar_del_grupo(id_socio, &mut socio);
+            self.socios.insert(id_socio, &socio);
+            Ok(())
+        }
+
+        /// Saca al dependiente dado de su grupo familiar y guarda al titular (el dependiente no se guarda acá).
+        fn quitar_del_grupo(&mut self, id_socio: u64, socio: &mut Socio) {
+            let id_titular = socio.titular.take().expect("El socio debe ser dependiente");
+            let mut titular = self.socios.get(id_titular).expect("El titular debe existir");
+            titular.dependientes.retain(|&id| id != id_socio);
+            self.socios.insert(id_titular, &titular);
+            self.env().emit_event(DependienteQuitado { dni_titular: titular.dni, dni: socio.dni, cuenta: self.env().caller() });
+        }
+
+        /// Establece el porcentaje de descuento para los pagos de los grupos familiares con algún dependiente activo.
+        /// Se aplica sobre el precio de todo el grupo, y se acumula con la bonificación por pagos consecutivos.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si no es menor a 100.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_porcentaje_descuento_familiar(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if nuevo_valor >= 100 {
+                return Err(ClubError::ValorInvalido);
+            }
+            self.porcentaje_descuento_familiar = nuevo_valor;
+            self.env().emit_event(DescuentoFamiliarCambiado {
+                porcentaje: nuevo_valor,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Retorna el porcentaje de descuento para los grupos familiares.
+        #[ink(message)]
+        pub fn get_porcentaje_descuento_familiar(&self) -> u8 {
+            self.porcentaje_descuento_familiar
+        }
+
+        /// Retorna lo que paga el socio dado (que no es dependiente) por un período con el vencimiento dado, sin bonificaciones:
+        /// su precio, más el de sus dependientes activos con el descuento familiar si tiene alguno.
+        fn precio_facturado(&self, socio: &Socio, vencimiento: Fecha) -> Result<u128, ClubError> {
+            let mut precio = self.precio_socio(socio.categoria, &socio.actividades_elegidas, vencimiento)?;
+            let mut hay_dependientes = false;
+            for &id in socio.dependientes.iter() {
+                let dependiente = self.socios.get(id).expect("El dependiente debe existir");
+                if dependiente.es_activo() {
+                    let precio_dependiente = self.precio_socio(dependiente.categoria, &dependiente.actividades_elegidas, vencimiento)?;
+                    precio = precio.checked_add(precio_dependiente).ok_or(ClubError::Desbordamiento)?;
+                    hay_dependientes = true;
+                }
+            }
+            if hay_dependientes && self.porcentaje_descuento_familiar > 0 {
+                let procentaje_del_total = (100 - self.porcentaje_descuento_familiar) as u128;
+                precio = precio.checked_mul(procentaje_del_total).ok_or(ClubError::Desbordamiento)? / 100;
+            }
+            Ok(precio)
+        }
+
+        /// Establece los días que tiene un socio nuevo o readmitido para hacer su primer pago.
+        /// 
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_dias_primer_vencimiento(&mut self, dias: u16) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            self.dias_primer_vencimiento = dias;
+            self.emitir_facturacion_cambiada();
+            Ok(())
+        }
+
+        /// Retorna los días que tiene un socio nuevo o readmitido para hacer su primer pago.
+        #[ink(message)]
+        pub fn get_dias_primer_vencimiento(&self) -> u16 {
+            self.dias_primer_vencimiento
+        }
+
+        /// Establece cada cuánto vencen los pagos. Sólo afecta a los pagos que se generen de ahora en más.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el ciclo es inválido (ver [CicloFacturacion::assert_valido]).
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_ciclo_facturacion(&mut self, ciclo: CicloFacturacion) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            ciclo.assert_valido()?;
+            self.ciclo_facturacion = ciclo;
+            self.emitir_facturacion_cambiada();
+            Ok(())
+        }
+
+        /// Retorna cada cuánto vencen los pagos.
+        #[ink(message)]
+        pub fn get_ciclo_facturacion(&self) -> CicloFacturacion {
+            self.ciclo_facturacion
+        }
+
+        fn emitir_facturacion_cambiada(&self) {
+            self.env().emit_event(FacturacionCambiada {
+                dias_primer_vencimiento: self.dias_primer_vencimiento,
+                ciclo_facturacion: self.ciclo_facturacion,
+                cuenta: self.env().caller(),
+            });
+        }
+
+        /// Retorna el vencimiento del primer pago de un socio que se registra hoy.
+        fn primer_vencimiento(&self) -> Fecha {
+            let mut vencimiento = self.obtener_fecha_actual();
+            vencimiento.sumar_dias(self.dias_primer_vencimiento as i32);
+            vencimiento
+        }
+
+        /// Retorna el id y el socio con el dni dado, o [ClubError::SocioInexistente] si no existe.
+        fn socio_por_dni(&self, dni: u128) -> Result<(u64, Socio), ClubError> {
+            let id_socio = self.buscar_socio(dni).ok_or(ClubError::SocioInexistente)?;
+            Ok((id_socio, self.socios.get(id_socio).expect("El socio debe existir")))
+        }
+
+        /// Retorna el id y el socio que paga por el socio activo con el dni dado: el titular de su grupo familiar
+        /// si es dependiente, o él mismo si no.
+        /// 
+        /// Retorna [ClubError::SocioNoActivo] si el socio o su titular no están activos.
+        fn socio_facturado(&self, dni: u128) -> Result<(u64, Socio), ClubError> {
+            let (id_socio, socio) = self.socio_por_dni(dni)?;
+            if !socio.es_activo() {
+                return Err(ClubError::SocioNoActivo);
+            }
+            match socio.titular {
+                None => Ok((id_socio, socio)),
+                Some(id_titular) => {
+                    let titular = self.socios.get(id_titular).expect("El titular debe existir");
+                    if !titular.es_activo() {
+                        return Err(ClubError::SocioNoActivo);
+                    }
+                    Ok((id_titular, titular))
+                }
+            }
+        }
+
+        /// Guarda el socio con su nuevo estado y emite el evento correspondiente.
+        fn guardar_estado(&mut self, id_socio: u64, socio: &Socio) {
+            self.socios.insert(id_socio, socio);
+            self.env().emit_event(EstadoSocioCambiado { dni: socio.dni, estado: socio.estado, cuenta: self.env().caller() });
+        }
+
+        /// Busca un socio y retorna un Option con su id en caso de existir en el registro, caso contrario
+        /// retorna None.
+        fn buscar_socio(&self, dni: u128) -> Option<u64> {
+            self.id_por_dni.get(dni)
+        }
+
+        /// Devuelve el socio con la id dada, o [ClubError::SocioInexistente] si no existe.
+        /// 
+        /// Requiere el rol [Rol::Auditor].
+        #[ink(message)]
+        pub fn get_socio(&self, id: u64) -> Result<Socio, ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
+            self.socios.get(id).ok_or(ClubError::SocioInexistente)
+        }
+
+        /// Vincula la cuenta dada con el socio del dni ingresado, reemplazando la que tuviera antes.
+        /// Si la cuenta había reclamado otro dni, el reclamo se descarta.
+        /// 
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn vincular_cuenta(&mut self, dni: u128, cuenta: AccountId) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            self._vincular_cuenta(dni, cuenta)
+        }
+        fn _vincular_cuenta(&mut self, dni: u128, cuenta: AccountId) -> Result<(), ClubError> {
+            let id_socio = self.buscar_socio(dni).ok_or(ClubError::SocioInexistente)?;
+            if self.id_por_cuenta.get(cuenta).is_some() {
+                return Err(ClubError::CuentaYaVinculada);
+            }
+            let mut socio = self.socios.get(id_socio).expect("El socio debe existir");
+            if let Some(anterior) = socio.cuenta {
+                self.id_por_cuenta.remove(anterior);
+            }
+            socio.cuenta = Some(cuenta);
+            self.socios.insert(id_socio, &socio);
+            self.id_por_cuenta.insert(cuenta, &id_socio);
+            self.reclamos.remove(dni);
+            self.env().emit_event(CuentaVinculada { dni, cuenta });
+            Ok(())
+        }
+
+        /// La cuenta actual reclama ser la del socio con el dni dado. El reclamo queda pendiente hasta que
+        /// alguien con el rol [Rol::Secretaria] lo apruebe con [Club::aprobar_reclamo].
+        /// Un nuevo reclamo del mismo dni reemplaza al anterior.
+        #[ink(message)]
+        pub fn reclamar_cuenta(&mut self, dni: u128) -> Result<(), ClubError> {
+            let cuenta = self.env().caller();
+            if self.buscar_socio(dni).is_none() {
+                return Err(ClubError::SocioInexistente);
+            }
+            if self.id_por_cuenta.get(cuenta).is_some() {
+                return Err(ClubError::CuentaYaVinculada);
+            }
+            self.reclamos.insert(dni, &cuenta);
+            self.env().emit_event(CuentaReclamada { dni, cuenta });
+            Ok(())
+        }
+
+        /// Retorna la cuenta que reclamó el dni dado, si hay alguna.
+        #[ink(message)]
+        pub fn get_reclamo(&self, dni: u128) -> Option<AccountId> {
+            self.reclamos.get(dni)
+        }
+
+        /// Aprueba el reclamo pendiente del dni dado, vinculando la cuenta que lo hizo con el socio.
+        /// 
+        /// Requiere el rol [Rol::Secretaria].
+        #[ink(message)]
+        pub fn aprobar_reclamo(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Secretaria)?;
+            let cuenta = self.reclamos.get(dni).ok_or(ClubError::SinReclamo)?;
+            self._vincular_cuenta(dni, cuenta)
+        }
+
+        /// Retorna el id del socio vinculado a la cuenta actual.
+        fn mi_id_socio(&self) -> Result<u64, ClubError> {
+            self.id_por_cuenta.get(self.env().caller()).ok_or(ClubError::CuentaNoVinculada)
+        }
+
+        /// Retorna el socio vinculado a la cuenta actual.
+        #[ink(message)]
+        pub fn mi_socio(&self) -> Result<Socio, ClubError> {
+            let id_socio = self.mi_id_socio()?;
+            Ok(self.socios.get(id_socio).expect("El socio debe existir"))
+        }
+
+        /// Retorna el pago pendiente del socio vinculado a la cuenta actual, o el de su grupo familiar si es dependiente.
+        /// 
+        /// Retorna [ClubError::SocioNoActivo] si el socio está dado de baja, ya que no tiene pagos pendientes.
+        #[ink(message)]
+        pub fn mi_pago_pendiente(&self) -> Result<Pago, ClubError> {
+            let id_socio = self.mi_id_socio()?;
+            let socio = self.mi_socio()?;
+            if socio.estado == EstadoSocio::Baja {
+                return Err(ClubError::SocioNoActivo);
+            }
+            let id_facturado = socio.titular.unwrap_or(id_socio);
+            Ok(self.pagos.get(self.buscar_ultimo_pago(id_facturado)).expect("El pago pendiente debe existir"))
+        }
+
+        /// El socio vinculado a la cuenta actual paga su pago pendiente con los tokens transferidos
+        /// (ver [Club::realizar_pago_con_tokens]).
+        #[ink(message, payable)]
+        pub fn pagar_mi_cuota(&mut self) -> Result<(), ClubError> {
+            let dni = self.mi_socio()?.dni;
+            self._realizar_pago_con_tokens(dni)
+        }
+
+        /// Obtiene id del último pago pendiente del socio dado, que no puede estar dado de baja.
+        fn buscar_ultimo_pago(&self, id_socio: u64) -> u64 {
+            self.pago_pendiente.get(id_socio).expect("Todo socio que no está dado de baja debe tener registrado el siguiente pago pendiente")
+        }
+
+        /// Guarda un nuevo pago y lo agrega a los pagos de su socio. Si no está pagado, pasa a ser el pago pendiente del socio.
+        /// 
+        /// Retorna el id del pago.
+        fn agregar_pago(&mut self, pago: &Pago) -> u64 {
+            let id_pago = self.agregar_al_historial(pago);
+            if !pago.es_pagado() {
+                self.pago_pendiente.insert(pago.id_socio, &id_pago);
+            }
+            id_pago
+        }
+
+        /// Guarda un nuevo pago y lo agrega a los pagos de su socio, sin cambiar su pago pendiente.
+        /// 
+        /// Retorna el id del pago.
+        fn agregar_al_historial(&mut self, pago: &Pago) -> u64 {
+            let id_pago = self.cantidad_pagos;
+            let n = self.cantidad_pagos_por_socio.get(pago.id_socio).unwrap_or(0);
+            self.pagos.insert(id_pago, pago);
+            self.pagos_por_socio.insert((pago.id_socio, n), &id_pago);
+            self.cantidad_pagos_por_socio.insert(pago.id_socio, &(n + 1));
+            self.cantidad_pagos += 1;
+            id_pago
+        }
+
+        /// Retorna los ids y los pagos impagos que la facturación generó al socio dado después del pendiente, en orden.
+        fn buscar_pagos_facturados(&self, id_socio: u64) -> (Vec<u64>, Vec<Pago>) {
+            let ids = self.pagos_facturados.get(id_socio).unwrap_or_default();
+            let pagos = ids.iter().map(|&id_pago| self.pagos.get(id_pago).expect("El pago facturado debe existir")).collect();
+            (ids, pagos)
+        }
+
+        /// Cancela el pago pendiente y los facturados del socio dado, que deja de tener pagos pendientes.
+        /// 
+        /// Retorna los pagos cancelados.
+        fn cancelar_pagos_impagos(&mut self, id_socio: u64) -> Vec<Pago> {
+            let (mut ids, mut pagos) = self.buscar_pagos_facturados(id_socio);
+            let id_pendiente = self.buscar_ultimo_pago(id_socio);
+            ids.insert(0, id_pendiente);
+            pagos.insert(0, self.pagos.get(id_pendiente).expect("El pago pendiente debe existir"));
+            for (&id_pago, pago) in ids.iter().zip(pagos.iter_mut()) {
+                pago.cancelado = true;
+                self.pagos.insert(id_pago, pago);
+            }
+            self.pago_pendiente.remove(id_socio);
+            self.pagos_facturados.remove(id_socio);
+            pagos
+        }
+
+        /// Genera los pagos de los períodos que ya empezaron y que todavía no tienen pago, para los próximos `cantidad` socios
+        /// de la facturación en curso, en orden de id. Así la deuda de los socios que no pagan se acumula en un pago por período,
+        /// en vez de quedar sólo el pendiente. Los pagos se generan como al pagar (con el precio vigente en su vencimiento
+        /// y el saldo a favor del socio), pero sin bonificación, y quedan impagos después del pendiente, que se sigue pagando primero.
+        /// 
+        /// Como la facturación sólo genera los pagos que faltan, repetirla no genera nada nuevo, y se puede hacer de a partes para
+        /// no pasarse del límite de gas: cada llamada sigue desde el socio en el que terminó la anterior.
+        /// Los socios suspendidos y los dados de baja no se facturan, y los dependientes se facturan con su titular.
+        /// 
+        /// Retorna true si se terminó de facturar a todos los socios (y la próxima llamada empieza otra facturación desde el primero),
+        /// o [ClubError::ValorInvalido] si `cantidad` es 0.
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn facturar(&mut self, cantidad: u64) -> Result<bool, ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            if cantidad == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            let fecha_actual = self.obtener_fecha_actual();
+            let desde = self.siguiente_socio_a_facturar;
+            let hasta = desde.saturating_add(cantidad).min(self.cantidad_socios);
+            for id_socio in desde..hasta {
+                self.facturar_socio(id_socio, fecha_actual)?;
+            }
+            let terminada = hasta == self.cantidad_socios;
+            self.siguiente_socio_a_facturar = if terminada { 0 } else { hasta };
+            Ok(terminada)
+        }
+
+        /// Retorna el id del próximo socio a facturar en la facturación en curso, o 0 si no hay ninguna empezada.
+        #[ink(message)]
+        pub fn get_siguiente_socio_a_facturar(&self) -> u64 {
+            self.siguiente_socio_a_facturar
+        }
+
+        /// Genera los pagos de los períodos que ya empezaron a la fecha dada y que el socio dado todavía no tiene.
+        fn facturar_socio(&mut self, id_socio: u64, fecha_actual: Fecha) -> Result<(), ClubError> {
+            let mut socio = self.socios.get(id_socio).expect("El socio debe existir");
+            if !socio.es_activo() || socio.titular.is_some() {
+                return Ok(());
+            }
+            let (mut ids, facturados) = self.buscar_pagos_facturados(id_socio);
+            let mut ultimo = match facturados.last() {
+                Some(pago) => pago.clone(),
+                None => self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir"),
+            };
+            // El período de cada pago empieza cuando vence el anterior
+            let mut nuevos = Vec::new();
+            while fecha_actual.es_mayor(&ultimo.vencimiento) {
+                let mut pago = self.siguiente_pago(&socio, &ultimo)?;
+                Self::aplicar_saldo_a_favor(&mut socio, &mut pago);
+                nuevos.push(pago.clone());
+                ultimo = pago;
+            }
+            if nuevos.is_empty() {
+                return Ok(());
+            }
+            let monto = Self::total_pagos(&nuevos)?;
+            for pago in nuevos.iter() {
+                ids.push(self.agregar_al_historial(pago));
+            }
+            self.pagos_facturados.insert(id_socio, &ids);
+            self.socios.insert(id_socio, &socio);
+            self.env().emit_event(PagosFacturados { dni: socio.dni, cantidad: nuevos.len() as u32, monto });
+            Ok(())
+        }
+
+        /// Se registra el pago del dni ingresado solo si el monto ingresado es igual al monto a pagar
+        /// según su pago pendiente (ver [Club::get_monto_a_pagar]), y el socio está activo (si no, retorna [ClubError::SocioNoActivo]).
+        /// Si el socio es dependiente de un grupo familiar, se paga el pago pendiente del grupo.
+        /// 
+        /// Una vez registrado el pago actual se genera automaticamente el siguiente pago del usuario con su respectivo vencimiento
+        /// y bonificación.
+        #[ink(message)]
+        pub fn realizar_pago(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {
+            self._realizar_pago(dni, monto)
+        }
+        fn _realizar_pago(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            self.registrar_pago(dni, 1, monto, false)
+        }
+
+        /// Realiza el pago pendiente del dni ingresado con los tokens transferidos, que quedan en el contrato.
+        /// El valor transferido debe ser igual al monto del pago pendiente.
+        /// 
+        /// Como el pago se hace con fondos reales, cualquier cuenta lo puede realizar, sin importar la política de autorización.
+        #[ink(message, payable)]
+        pub fn realizar_pago_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {
+            self._realizar_pago_con_tokens(dni)
+        }
+        fn _realizar_pago_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {
+            let monto = self.env().transferred_value();
+            let fondos = self.fondos.checked_add(monto).ok_or(ClubError::Desbordamiento)?;
+            self.registrar_pago(dni, 1, monto, true)?;
+            self.fondos = fondos;
+            Ok(())
+        }
+
+        /// Registra un pago parcial del pago pendiente del dni ingresado. El pago pendiente recién se considera pagado,
+        /// con la fecha de hoy, cuando los pagos parciales cubren el monto completo, incluido el recargo si está atrasado.
+        /// 
+        /// Retorna [ClubError::MontoIncorrecto] si el monto supera lo que falta pagar, o [ClubError::ValorInvalido] si es 0.
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn realizar_pago_parcial(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            self.registrar_pago_parcial(dni, monto, false)
+        }
+
+        /// Registra un pago parcial del pago pendiente del dni ingresado con los tokens transferidos (ver [Club::realizar_pago_parcial]).
+        /// 
+        /// Al igual que [Club::realizar_pago_con_tokens], cualquier cuenta lo puede realizar.
+        #[ink(message, payable)]
+        pub fn realizar_pago_parcial_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {
+            let monto = self.env().transferred_value();
+            let fondos = self.fondos.checked_add(monto).ok_or(ClubError::Desbordamiento)?;
+            self.registrar_pago_parcial(dni, monto, true)?;
+            self.fondos = fondos;
+            Ok(())
+        }
+
+        /// Registra un pago parcial del pago pendiente del socio, sin verificar la autorización.
+        /// Si es `con_tokens`, el pago parcial se guarda como pagado con tokens por la cuenta actual.
+        fn registrar_pago_parcial(&mut self, dni: u128, monto: u128, con_tokens: bool) -> Result<(), ClubError> {
+            if monto == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            let (id_socio, socio) = self.socio_facturado(dni)?;
+            let id_pago = self.buscar_ultimo_pago(id_socio);
+            let mut pago = self.pagos.get(id_pago).expect("El pago pendiente debe existir");
+            let recargo = self.calcular_recargo(&pago, self.obtener_fecha_actual())?;
+            let restante = pago.get_monto_restante().checked_add(recargo).ok_or(ClubError::Desbordamiento)?;
+            if monto > restante {
+                return Err(ClubError::MontoIncorrecto);
+            }
+            if monto == restante {
+                // Con esto se completa el pago
+                return self.registrar_pago(dni, 1, monto, con_tokens);
+            }
+            pago.pagos_parciales.push((self.obtener_fecha_actual(), monto));
+            if con_tokens {
+                pago.pagos_con_tokens.push((self.env().caller(), monto));
+            }
+            self.pagos.insert(id_pago, &pago);
+            self.env().emit_event(PagoParcialRealizado {
+                dni: socio.dni,
+                monto,
+                restante: restante - monto,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Realiza el pago adelantado de los próximos `periodos` pagos del dni ingresado, empezando por el pendiente.
+        /// El monto debe ser igual al total a pagar (ver [Club::get_monto_pago_adelantado]).
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si `periodos` es 0.
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn realizar_pago_adelantado(&mut self, dni: u128, periodos: u16, monto: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            self.registrar_pago(dni, periodos, monto, false)
+        }
+
+        /// Realiza el pago adelantado de los próximos `periodos` pagos del dni ingresado con los tokens transferidos,
+        /// que deben ser iguales al total a pagar (ver [Club::get_monto_pago_adelantado]).
+        /// 
+        /// Al igual que [Club::realizar_pago_con_tokens], cualquier cuenta lo puede realizar.
+        #[ink(message, payable)]
+        pub fn realizar_pago_adelantado_con_tokens(&mut self, dni: u128, periodos: u16) -> Result<(), ClubError> {
+            let monto = self.env().transferred_value();
+            let fondos = self.fondos.checked_add(monto).ok_or(ClubError::Desbordamiento)?;
+            self.registrar_pago(dni, periodos, monto, true)?;
+            self.fondos = fondos;
+            Ok(())
+        }
+
+        /// Retorna el total a pagar para adelantar los próximos `periodos` pagos del dni ingresado,
+        /// empezando por el pendiente.
+        #[ink(message)]
+        pub fn get_monto_pago_adelantado(&self, dni: u128, periodos: u16) -> Result<u128, ClubError> {
+            let (id_socio, mut socio) = self.socio_facturado(dni)?;
+            let pendiente = self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir");
+            let (_, facturados) = self.buscar_pagos_facturados(id_socio);
+            let pagado_antes = pendiente.get_monto_pagado();
+            let (pagados, _) = self.liquidar_periodos(&mut socio, pendiente, facturados, periodos)?;
+            Ok(Self::total_pagos(&pagados)? - pagado_antes)
+        }
+
+        /// Retorna lo que falta pagar hoy para completar el pago pendiente del dni ingresado,
+        /// incluido el recargo si ya pasó el vencimiento.
+        #[ink(message)]
+        pub fn get_monto_a_pagar(&self, dni: u128) -> Result<u128, ClubError> {
+            self.get_monto_pago_adelantado(dni, 1)
+        }
+
+        /// Retorna el recargo que se le cobraría hoy al pago pendiente del dni ingresado.
+        #[ink(message)]
+        pub fn get_recargo_pendiente(&self, dni: u128) -> Result<u128, ClubError> {
+            let (id_socio, _) = self.socio_facturado(dni)?;
+            let pendiente = self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir");
+            self.calcular_recargo(&pendiente, self.obtener_fecha_actual())
+        }
+
+        /// Retorna el estado de cuenta del socio con el dni dado: cada período que adeuda (el pago pendiente y los facturados,
+        /// ver [Club::facturar]) con lo que falta pagar, incluido el recargo que se le cobraría hoy, y la deuda total.
+        /// Si el socio es dependiente de un grupo familiar, retorna el del grupo. Los socios dados de baja no adeudan nada.
+        /// 
+        /// Retorna [ClubError::SocioInexistente] si no hay ningún socio con ese dni.
+        /// Requiere el rol [Rol::Auditor].
+        #[ink(message)]
+        pub fn estado_de_cuenta(&self, dni: u128) -> Result<EstadoDeCuenta, ClubError> {
+            self.verificar_rol(Rol::Auditor)?;
+            let (mut id_socio, mut socio) = self.socio_por_dni(dni)?;
+            if let Some(id_titular) = socio.titular {
+                id_socio = id_titular;
+                socio = self.socios.get(id_titular).expect("El titular debe existir");
+            }
+            let fecha_actual = self.obtener_fecha_actual();
+            let mut periodos = Vec::new();
+            let mut deuda_total: u128 = 0;
+            if socio.estado != EstadoSocio::Baja {
+                let pendiente = self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir");
+                let (_, facturados) = self.buscar_pagos_facturados(id_socio);
+                for pago in core::iter::once(pendiente).chain(facturados) {
+                    let periodo = PeriodoAdeudado {
+                        vencimiento: pago.vencimiento,
+                        categoria: pago.categoria,
+                        monto: pago.monto,
+                        pagado: pago.get_monto_pagado(),
+                        dias_de_atraso: pago.vencimiento.dias_hasta(&fecha_actual).max(0) as u32,
+                        recargo: self.calcular_recargo(&pago, fecha_actual)?,
+                        es_bonificado: pago.es_descuento,
+                        descuento_cupon: pago.descuento_cupon,
+                        saldo_aplicado: pago.saldo_aplicado,
+                    };
+                    deuda_total = deuda_total.checked_add(periodo.get_deuda()).ok_or(ClubError::Desbordamiento)?;
+                    periodos.push(periodo);
+                }
+            }
+            Ok(EstadoDeCuenta { dni: socio.dni, periodos, saldo_a_favor: socio.saldo_a_favor, deuda_total })
+        }
+
+        /// Establece el recargo que se cobra a los pagos que se completan después de su vencimiento.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el recargo tiene algún valor en 0 (para no cobrar recargos está [Recargo::Ninguno]).
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_recargo(&mut self, recargo: Recargo) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            recargo.assert_valido()?;
+            self.recargo = recargo;
+            self.env().emit_event(RecargoCambiado { recargo, cuenta: self.env().caller() });
+            Ok(())
+        }
+
+        /// Retorna el recargo que se cobra a los pagos atrasados.
+        #[ink(message)]
+        pub fn get_recargo(&self) -> Recargo {
+            self.recargo
+        }
+
+        /// Retorna el recargo que corresponde al pago si se completa en la fecha dada.
+        fn calcular_recargo(&self, pago: &Pago, fecha: Fecha) -> Result<u128, ClubError> {
+            let dias_de_atraso = pago.vencimiento.dias_hasta(&fecha).max(0) as u32;
+            self.recargo.calcular(pago.monto, dias_de_atraso).ok_or(ClubError::Desbordamiento)
+        }
+
+        /// Establece el porcentaje de descuento para los pagos que se adelantan, sin contar el pendiente.
+        /// Con 0 no hay descuento. No se acumula con la bonificación por pagos consecutivos: se aplica el mayor de los dos.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si no es menor a 100.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn set_porcentaje_descuento_pago_adelantado(&mut self, nuevo_valor: u8) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if nuevo_valor >= 100 {
+                return Err(ClubError::ValorInvalido);
+            }
+            self.porcentaje_descuento_adelantado = nuevo_valor;
+            self.env().emit_event(DescuentoPagoAdelantadoCambiado {
+                porcentaje: nuevo_valor,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Retorna el porcentaje de descuento para los pagos que se adelantan.
+        #[ink(message)]
+        pub fn get_porcentaje_descuento_pago_adelantado(&self) -> u8 {
+            self.porcentaje_descuento_adelantado
+        }
+
+        /// Crea un cupón de descuento con el código dado, que se puede usar al registrar un socio
+        /// (ver [Club::registrar_nuevo_socio_con_cupon]) o en un pago pendiente (ver [Club::aplicar_cupon]),
+        /// desde la fecha `desde` hasta la fecha `hasta` inclusive, hasta `usos_maximos` veces en total.
+        /// Si `categorias` no está vacío, sólo se puede usar en pagos de esas categorías.
+        /// 
+        /// Retorna [ClubError::NombreRepetido] si ya existe un cupón con ese código, [ClubError::CategoriaInexistente] si
+        /// alguna categoría no existe, o [ClubError::ValorInvalido] si el descuento es inválido (ver [Descuento::assert_valido]),
+        /// `hasta` es anterior a `desde`, o `usos_maximos` es 0.
+        /// Requiere el rol [Rol::Administrador].
+        #[ink(message)]
+        pub fn crear_cupon(&mut self, codigo: String, descuento: Descuento, desde: Fecha, hasta: Fecha, usos_maximos: u32, categorias: Vec<IdCategoria>) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Administrador)?;
+            if self.cupones.contains(&codigo) {
+                return Err(ClubError::NombreRepetido);
+            }
+            descuento.assert_valido()?;
+            if desde.es_mayor(&hasta) || usos_maximos == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            if categorias.iter().any(|&categoria| categoria as usize >= self.categorias.len()) {
+                return Err(ClubError::CategoriaInexistente);
+            }
+            let cupon = Cupon { codigo: codigo.clone(), descuento, desde, hasta, usos_maximos, usos: 0, categorias };
+            self.cupones.insert(&codigo, &cupon);
+            self.env().emit_event(CuponCreado { codigo, descuento, cuenta: self.env().caller() });
+            Ok(())
+        }
+
+        /// Retorna el cupón con el código dado, o [ClubError::CuponInexistente] si no existe.
+        #[ink(message)]
+        pub fn get_cupon(&self, codigo: String) -> Result<Cupon, ClubError> {
+            self.cupones.get(&codigo).ok_or(ClubError::CuponInexistente)
+        }
+
+        /// Usa el cupón con el código dado en el pago pendiente del dni ingresado (o el de su grupo familiar),
+        /// descontando su monto. Cada pago puede tener un solo cupón.
+        /// 
+        /// Retorna [ClubError::CuponNoAplicable] si el pago ya tiene un cupón, o si hoy no se puede usar el cupón en la
+        /// categoría del pago (ver [Cupon::verificar_aplicable]).
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn aplicar_cupon(&mut self, dni: u128, codigo: String) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            let (id_socio, socio) = self.socio_facturado(dni)?;
+            let pendiente = self.pagos.get(self.buscar_ultimo_pago(id_socio)).expect("El pago pendiente debe existir");
+            if pendiente.cupon.is_some() {
+                return Err(ClubError::CuponNoAplicable);
+            }
+            let cupon = self.cupon_aplicable(&codigo, pendiente.categoria)?;
+            self.usar_cupon(socio.dni, id_socio, cupon);
+            Ok(())
+        }
+
+        /// Retorna el cupón con el código dado si hoy se puede usar en un pago de la categoría dada.
+        fn cupon_aplicable(&self, codigo: &String, categoria: IdCategoria) -> Result<Cupon, ClubError> {
+            let cupon = self.cupones.get(codigo).ok_or(ClubError::CuponInexistente)?;
+            cupon.verificar_aplicable(categoria, self.obtener_fecha_actual())?;
+            Ok(cupon)
+        }
+
+        /// Descuenta el cupón (que ya se sabe que se puede usar) del pago pendiente del socio, lo guarda en el pago,
+        /// y cuenta el uso. El monto del pago no baja de lo que ya se pagó en partes.
+        fn usar_cupon(&mut self, dni: u128, id_socio: u64, mut cupon: Cupon) {
+            let id_pago = self.buscar_ultimo_pago(id_socio);
+            let mut pago = self.pagos.get(id_pago).expect("El pago pendiente debe existir");
+            let descuento = cupon.descuento.calcular(pago.monto).min(pago.monto.saturating_sub(pago.get_monto_pagado()));
+            pago.monto -= descuento;
+            pago.cupon = Some(cupon.codigo.clone());
+            pago.descuento_cupon = descuento;
+            cupon.usos += 1;
+            self.pagos.insert(id_pago, &pago);
+            self.cupones.insert(&cupon.codigo, &cupon);
+            self.env().emit_event(CuponAplicado { dni, codigo: cupon.codigo, descuento, cuenta: self.env().caller() });
+        }
+
+        /// Registra los próximos `periodos` pagos del socio, empezando por el pendiente, sin verificar la autorización.
+        /// Si es `con_tokens`, los pagos se guardan como pagados con tokens por la cuenta actual.
+        fn registrar_pago(&mut self, dni: u128, periodos: u16, monto: u128, con_tokens: bool) -> Result<(), ClubError> {
+            let (id_socio, mut socio) = self.socio_facturado(dni)?;
+            let dni = socio.dni;
+            let id_pago = self.buscar_ultimo_pago(id_socio);
+            let pendiente = self.pagos.get(id_pago).expect("El pago pendiente debe existir");
+            let (ids_facturados, facturados) = self.buscar_pagos_facturados(id_socio);
+            // Lo que ya se pagó del pendiente con pagos parciales
+            let pagado_antes = pendiente.get_monto_pagado();
+            let (mut pagados, nuevo_pendiente) = self.liquidar_periodos(&mut socio, pendiente, facturados, periodos)?;
+            if Self::total_pagos(&pagados)? - pagado_antes != monto {
+                return Err(ClubError::MontoIncorrecto);
+            }
+
+            if con_tokens {
+                for (i, pago) in pagados.iter_mut().enumerate() {
+                    let monto_pagado = pago.get_monto_pagado() - if i == 0 { pagado_antes } else { 0 };
+                    pago.pagos_con_tokens.push((self.env().caller(), monto_pagado));
+                }
+            }
+            for (i, pago) in pagados.iter().enumerate() {
+                self.env().emit_event(PagoRealizado {
+                    dni,
+                    monto: pago.get_monto_pagado() - if i == 0 { pagado_antes } else { 0 },
+                    fecha: pago.pagado.unwrap(),
+                    a_tiempo: pago.es_pagado_a_tiempo().unwrap(),
+                    cuenta: self.env().caller(),
+                });
+                let siguiente = pagados.get(i + 1).unwrap_or(&nuevo_pendiente);
+                if siguiente.es_descuento {
+                    self.env().emit_event(BonificacionOtorgada { dni, monto: siguiente.monto, vencimiento: siguiente.vencimiento });
+                }
+            }
+            // Recién ahora se guardan los cambios, para no dejar nada a medias si hubo un error.
+            // Los primeros pagos son el pendiente y los facturados, que ya están guardados, y el resto son nuevos
+            let mut ids_existentes = ids_facturados;
+            ids_existentes.insert(0, id_pago);
+            for (i, pago) in pagados.iter().chain(core::iter::once(&nuevo_pendiente)).enumerate() {
+                match ids_existentes.get(i) {
+                    Some(&id) => { self.pagos.insert(id, pago); }
+                    None => { self.agregar_pago(pago); }
+                }
+            }
+            let periodos = periodos as usize;
+            if let Some(&id_nuevo_pendiente) = ids_existentes.get(periodos) {
+                self.pago_pendiente.insert(id_socio, &id_nuevo_pendiente);
+            }
+            match ids_existentes.get(periodos + 1..) {
+                Some(restantes) if !restantes.is_empty() => { self.pagos_facturados.insert(id_socio, &restantes.to_vec()); }
+                _ => self.pagos_facturados.remove(id_socio),
+            }
+            self.socios.insert(id_socio, &socio);
+            Ok(())
+        }
+
+        /// Calcula cómo quedan los pagos del socio si se pagan hoy los próximos `periodos` pagos, empezando por el pendiente
+        /// y siguiendo por los `facturados` (ver [Club::facturar]), que nunca están pagados, sin guardar nada.
+        /// Si no alcanzan, se generan los siguientes.
+        /// Actualiza en el socio dado (que no se guarda acá) los pagos a tiempo consecutivos y el saldo a favor.
+        /// 
+        /// Retorna los pagos pagados (el primero es el pendiente) y el nuevo pago pendiente.
+        fn liquidar_periodos(&self, socio: &mut Socio, pendiente: Pago, facturados: Vec<Pago>, periodos: u16) -> Result<(Vec<Pago>, Pago), ClubError> {
+            if periodos == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            let fecha_actual = self.obtener_fecha_actual();
+            let mut pagados = Vec::new();
+            let mut facturados = facturados.into_iter();
+            let mut pago = pendiente;
+            // Sólo el pendiente y los facturados pueden estar atrasados
+            let mut ya_generado = true;
+            for n in 1..=periodos {
+                if ya_generado {
+                    pago.recargo = self.calcular_recargo(&pago, fecha_actual)?;
+                }
+                // Si el pendiente se venía pagando en partes, lo que falta es la última parte
+                if !pago.pagos_parciales.is_empty() {
+                    pago.pagos_parciales.push((fecha_actual, pago.get_monto_restante()));
+                }
+                pago.pagado = Some(fecha_actual);
+                pago.pagos_a_tiempo_previos = socio.pagos_a_tiempo_consecutivos;
+                // los pagos con descuento no cuentan para el siguiente descuento
+                if pago.es_pagado_a_tiempo().unwrap() && !pago.es_descuento {
+                    socio.pagos_a_tiempo_consecutivos += 1;
+                }
+
+                // El siguiente pago es el próximo facturado, o si no hay se genera
+                let nuevo_pago = match facturados.next() {
+                    Some(facturado) => facturado,
+                    None => {
+                        ya_generado = false;
+                        let mut nuevo_pago = self.siguiente_pago(socio, &pago)?;
+                        nuevo_pago.es_adelantado = n < periodos;
+
+                        // Aplicar bonificación, o el descuento por adelantado si el siguiente también se paga ahora
+                        let mut porcentaje_descuento = 0;
+                        if socio.pagos_a_tiempo_consecutivos >= self.cantidad_pagos_bonificacion {
+                            socio.pagos_a_tiempo_consecutivos = 0;
+                            nuevo_pago.es_descuento = true;
+                            porcentaje_descuento = self.porcentaje_bonificacion;
+                        }
+                        if nuevo_pago.es_adelantado {
+                            porcentaje_descuento = porcentaje_descuento.max(self.porcentaje_descuento_adelantado);
+                        }
+                        if porcentaje_descuento > 0 {
+                            let procentaje_del_total = (100 - porcentaje_descuento) as u128;
+                            nuevo_pago.monto = nuevo_pago.monto.checked_mul(procentaje_del_total)
+                                .ok_or(ClubError::Desbordamiento)? / 100;
+                        }
+                        Self::aplicar_saldo_a_favor(socio, &mut nuevo_pago);
+                        nuevo_pago
+                    }
+                };
+
+                pagados.push(pago);
+                pago = nuevo_pago;
+            }
+            Ok((pagados, pago))
+        }
+
+        /// Genera, sin guardarlo, el pago del período siguiente al del pago dado, con el precio del socio dado (que no es dependiente)
+        /// vigente en su vencimiento, sin descuentos ni saldo a favor.
+        fn siguiente_pago(&self, socio: &Socio, anterior: &Pago) -> Result<Pago, ClubError> {
+            let mut pago = anterior.clone();
+            pago.pagado = None;
+            pago.vencimiento = self.ciclo_facturacion.siguiente_vencimiento(anterior.vencimiento, socio.dia_de_vencimiento);
+            pago.es_descuento = false;
+            pago.es_adelantado = false;
+            pago.pagos_parciales = Vec::new();
+            pago.recargo = 0;
+            pago.categoria = socio.categoria;
+            pago.categoria_anterior = None;
+            pago.cupon = None;
+            pago.descuento_cupon = 0;
+            pago.pagos_con_tokens = Vec::new();
+            pago.saldo_aplicado = 0;
+            pago.monto = self.precio_facturado(socio, pago.vencimiento)?;
+            Ok(pago)
+        }
+
+        /// Descuenta del monto del pago que se está generando todo el saldo a favor del socio que se pueda.
+        fn aplicar_saldo_a_favor(socio: &mut Socio, pago: &mut Pago) {
+            let saldo_aplicado = socio.saldo_a_favor.min(pago.monto);
+            pago.monto -= saldo_aplicado;
+            pago.saldo_aplicado = saldo_aplicado;
+            socio.saldo_a_favor -= saldo_aplicado;
+        }
+
+        /// Suma el monto dado al saldo a favor del dni ingresado (o al del titular de su grupo familiar), por ejemplo
+        /// como compensación. El saldo se descuenta automáticamente de los próximos pagos que se generen.
+        /// 
+        /// Retorna [ClubError::ValorInvalido] si el monto es 0.
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn acreditar_saldo(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            self.registrar_saldo(dni, monto)
+        }
+
+        /// Suma los tokens transferidos al saldo a favor del dni ingresado (ver [Club::acreditar_saldo]).
+        /// 
+        /// Al igual que [Club::realizar_pago_con_tokens], cualquier cuenta lo puede realizar.
+        #[ink(message, payable)]
+        pub fn acreditar_saldo_con_tokens(&mut self, dni: u128) -> Result<(), ClubError> {
+            let monto = self.env().transferred_value();
+            let fondos = self.fondos.checked_add(monto).ok_or(ClubError::Desbordamiento)?;
+            self.registrar_saldo(dni, monto)?;
+            self.fondos = fondos;
+            Ok(())
+        }
+
+        /// Suma el monto al saldo a favor del socio, sin verificar la autorización.
+        fn registrar_saldo(&mut self, dni: u128, monto: u128) -> Result<(), ClubError> {
+            if monto == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            let (id_socio, mut socio) = self.socio_facturado(dni)?;
+            socio.saldo_a_favor = socio.saldo_a_favor.checked_add(monto).ok_or(ClubError::Desbordamiento)?;
+            self.socios.insert(id_socio, &socio);
+            self.env().emit_event(SaldoAcreditado {
+                dni: socio.dni,
+                monto,
+                saldo: socio.saldo_a_favor,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Retorna el saldo a favor del dni ingresado, o el del titular de su grupo familiar si es dependiente.
+        #[ink(message)]
+        pub fn get_saldo_a_favor(&self, dni: u128) -> Result<u128, ClubError> {
+            let (_, socio) = self.socio_por_dni(dni)?;
+            match socio.titular {
+                Some(id_titular) => Ok(self.socios.get(id_titular).expect("El titular debe existir").saldo_a_favor),
+                None => Ok(socio.saldo_a_favor),
+            }
+        }
+
+        /// Revierte el último pago completado del dni ingresado (o el de su grupo familiar) que no fue revertido,
+        /// por ejemplo si se registró por error. Nada se borra: el pago revertido se conserva marcado con la fecha y la cuenta
+        /// que lo revirtió, el pago pendiente que se generó con él se cancela, y se vuelve a generar como pendiente
+        /// con el mismo monto y vencimiento. El socio vuelve a tener los pagos a tiempo consecutivos que tenía antes del pago,
+        /// y los tokens con los que se pagó se devuelven a las cuentas que los transfirieron. El saldo a favor usado en el pendiente
+        /// cancelado vuelve al socio, y si se le había aplicado un cupón, se le descuenta el uso para que se pueda volver a usar.
+        /// Llamándolo varias veces se revierten varios pagos, del más reciente al más antiguo.
+        /// Sólo se revierten pagos desde que el socio empezó a pagar por su cuenta por última vez (al registrarse, al ser readmitido
+        /// o al dejar un grupo familiar), no los de antes.
+        /// 
+        /// Retorna [ClubError::PagoNoRevertible] si no hay ningún pago para revertir, si ya hay pagos parciales del pendiente
+        /// o si ya se facturaron pagos después del pendiente (ver [Club::facturar]), o [ClubError::FondosInsuficientes] si los fondos no alcanzan para devolver los tokens.
+        /// Requiere el rol [Rol::Tesorero].
+        #[ink(message)]
+        pub fn revertir_pago(&mut self, dni: u128) -> Result<(), ClubError> {
+            self.verificar_rol(Rol::Tesorero)?;
+            let (id_socio, mut socio) = self.socio_facturado(dni)?;
+            let id_pendiente = self.buscar_ultimo_pago(id_socio);
+            let mut pendiente = self.pagos.get(id_pendiente).expect("El pago pendiente debe existir");
+            if !pendiente.pagos_parciales.is_empty() || self.pagos_facturados.contains(id_socio) {
+                return Err(ClubError::PagoNoRevertible);
+            }
+            // El último pago completado desde su primer pago, salteando los cancelados y los que ya se revirtieron
+            let cantidad = self.cantidad_pagos_por_socio.get(id_socio).unwrap_or(0);
+            let (id_pago, mut pago) = (socio.primer_pago..cantidad).rev()
+                .filter_map(|n| self.pagos_por_socio.get((id_socio, n)))
+                .map(|id_pago| (id_pago, self.pagos.get(id_pago).expect("El pago debe existir")))
+                .find(|(_, pago)| pago.es_pagado() && !pago.es_revertido())
+                .ok_or(ClubError::PagoNoRevertible)?;
+            let mut reembolso: Balance = 0;
+            for (_, monto) in pago.pagos_con_tokens.iter() {
+                reembolso = reembolso.checked_add(*monto).ok_or(ClubError::Desbordamiento)?;
+            }
+            if reembolso > self.fondos {
+                return Err(ClubError::FondosInsuficientes);
+            }
+            for &(cuenta, monto) in pago.pagos_con_tokens.iter() {
+                self.env().transfer(cuenta, monto).map_err(|_| ClubError::TransferenciaFallida)?;
+            }
+
+            let mut restaurado = pago.clone();
+            restaurado.pagado = None;
+            restaurado.es_adelantado = false;
+            restaurado.pagos_parciales = Vec::new();
+            restaurado.recargo = 0;
+            restaurado.pagos_con_tokens = Vec::new();
+            pago.revertido = Some((self.obtener_fecha_actual(), self.env().caller()));
+            pendiente.cancelado = true;
+            socio.pagos_a_tiempo_consecutivos = pago.pagos_a_tiempo_previos;
+            // El saldo a favor que se había usado en el pendiente vuelve al socio
+            socio.saldo_a_favor = socio.saldo_a_favor.checked_add(pendiente.saldo_aplicado).ok_or(ClubError::Desbordamiento)?;
+            let cupon_usado = pendiente.cupon.as_ref().and_then(|codigo| self.cupones.get(codigo));
+
+            self.fondos -= reembolso;
+            if let Some(mut cupon) = cupon_usado {
+                cupon.usos = cupon.usos.saturating_sub(1);
+                self.cupones.insert(&cupon.codigo, &cupon);
+            }
+            self.pagos.insert(id_pago, &pago);
+            self.pagos.insert(id_pendiente, &pendiente);
+            self.agregar_pago(&restaurado);
+            self.socios.insert(id_socio, &socio);
+            self.env().emit_event(PagoRevertido {
+                dni: socio.dni,
+                monto: pago.get_monto_pagado(),
+                reembolso,
+                cuenta: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// Retorna la suma de los montos de los pagos dados, con sus recargos.
+        fn total_pagos(pagos: &[Pago]) -> Result<u128, ClubError> {
+            pagos.iter().try_fold(0u128, |total, pago| {
+                total.checked_add(pago.monto)
+                    .and_then(|total| total.checked_add(pago.recargo))
+                    .ok_or(ClubError::Desbordamiento)
+            })
+        }
+
+        /// Retorna los fondos recaudados con [Club::realizar_pago_con_tokens] que todavía no fueron retirados.
+        #[ink(message)]
+        pub fn get_fondos(&self) -> Balance {
+            self.fondos
+        }
+
+        /// Transfiere fondos recaudados a cada una de las cuentas dadas, con su monto correspondiente.
+        /// 
+        /// Sólo el dueño puede retirar fondos, incluso con la política abierta.
+        /// Si el total supera los fondos recaudados no se transfiere nada.
+        #[ink(message)]
+        pub fn retirar_fondos(&mut self, destinos: Vec<(AccountId, Balance)>) -> Result<(), ClubError> {
+            if !self.soy_el_dueño() {
+                return Err(ClubError::NoEsDueño);
+            }
+            let mut total: Balance = 0;
+            for (_, monto) in destinos.iter() {
+                total = total.checked_add(*monto).ok_or(ClubError::Desbordamiento)?;
+            }
+            if total > self.fondos {
+                return Err(ClubError::FondosInsuficientes);
+            }
+            for (destino, monto) in destinos {
+                self.env().transfer(destino, monto).map_err(|_| ClubError::TransferenciaFallida)?;
+                self.fondos -= monto;
+                self.env().emit_event(FondosRetirados { destino, monto });
+            }
+            Ok(())
+        }
+
+        /// Retorna la fecha actual en un Struct con año, mes y día.
         #[ink(message)]
         pub fn obtener_fecha_actual(&self) -> Fecha {
             self._obtener_fecha_actual()
@@ -328,63 +2345,522 @@ pub mod trabajo_final {
     }
 
 
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    /// Los errores que pueden devolver los mensajes del [Club].
+    pub enum ClubError {
+        /// La cuenta actual no está autorizada a realizar la operación.
+        NoAutorizado,
+        /// La operación sólo la puede realizar el dueño del club.
+        NoEsDueño,
+        /// No existe ningún socio con el dni dado.
+        SocioInexistente,
+        /// Ya existe un socio registrado con el dni dado.
+        SocioYaRegistrado,
+        /// Las actividades elegidas no son válidas para la categoría (ver [Categoria::validar_eleccion]).
+        CategoriaInvalida,
+        /// No existe ninguna categoría con la id dada.
+        CategoriaInexistente,
+        /// No existe ninguna actividad con la id dada.
+        ActividadInexistente,
+        /// Ya existe una actividad o categoría con ese nombre.
+        NombreRepetido,
+        /// El monto ingresado no coincide con el monto del pago pendiente.
+        MontoIncorrecto,
+        /// La cuenta ya tiene el rol.
+        RolYaOtorgado,
+        /// La cuenta no tiene el rol.
+        RolNoOtorgado,
+        /// La cuenta ya está vinculada con un socio.
+        CuentaYaVinculada,
+        /// La cuenta actual no está vinculada con ningún socio.
+        CuentaNoVinculada,
+        /// Nadie reclamó el dni dado.
+        SinReclamo,
+        /// El socio no está en un estado que permita la operación (ver [EstadoSocio]).
+        EstadoInvalido,
+        /// El socio está suspendido o dado de baja, y la operación requiere un socio activo.
+        SocioNoActivo,
+        /// La operación no es válida para el grupo familiar del socio (ver [Club::agregar_dependiente]).
+        GrupoFamiliarInvalido,
+        /// No hay ningún pago que se pueda revertir (ver [Club::revertir_pago]).
+        PagoNoRevertible,
+        /// No existe ningún cupón con el código dado.
+        CuponInexistente,
+        /// El cupón no se puede usar en el pago (ver [Cupon::verificar_aplicable]), o el pago ya tiene un cupón.
+        CuponNoAplicable,
+        /// No hay ninguna propuesta de nuevo dueño.
+        SinPropuestaDeDueño,
+        /// La cuenta actual no es a la que se le propuso el club.
+        NoEsDueñoPropuesto,
+        /// El valor ingresado está fuera del rango permitido.
+        ValorInvalido,
+        /// El resultado de una operación no entra en el tipo numérico correspondiente.
+        Desbordamiento,
+        /// El club no tiene fondos suficientes para realizar la transferencia.
+        FondosInsuficientes,
+        /// Falló la transferencia de tokens.
+        TransferenciaFallida,
+    }
+
     #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
-    pub enum Actividad {
-        // Categoría C
-        Gimnasio,
-        // Categoría B (gimnasio + uno de estos deportes)
-        Futbol,
-        Basquet,
-        Rugby,
-        Hockey,
-        Natacion,
-        Tenis,
-        Paddle,
-        // Categoría A
-        // Todas
+    /// Los roles que el dueño le puede otorgar a otras cuentas para operar el club con la política cerrada.
+    pub enum Rol {
+        /// Puede registrar los pagos de los socios.
+        Tesorero,
+        /// Puede registrar socios.
+        Secretaria,
+        /// Puede cambiar el nombre, los precios, las categorías, las actividades y la configuración de la bonificación.
+        Administrador,
+        /// Puede leer la información de los socios y sus pagos.
+        Auditor,
     }
 
-    
-    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    /// Id de una actividad del club (su posición en [Club::get_actividades]).
+    pub type IdActividad = u32;
+    /// Id de una categoría del club (su posición en [Club::get_categorias]).
+    pub type IdCategoria = u32;
+    /// Cantidad máxima de socios que se pueden importar en una llamada a [Club::importar_socios].
+    pub const MAXIMO_SOCIOS_POR_IMPORTACION: usize = 100;
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq, Eq)]
     #[cfg_attr(
         feature = "std",
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
-    /// Las tres categorías posibles para un Socio.
-    /// La Categoría B contiene además la actividad deportiva elegida por el socio al registrarse.
-    pub enum Categoria{
-        CategoriaA,
-        CategoriaB(Actividad),
-        CategoriaC
+    /// Una categoría de socios del club, con su precio y las actividades a las que pueden acceder sus socios:
+    /// todas las incluidas, y la cantidad dada de actividades que cada socio elige entre las elegibles.
+    /// Cada socio puede elegir actividades extra hasta el máximo, pagando un precio adicional por cada una.
+    pub struct Categoria {
+        nombre: String,
+        /// Cada precio con la fecha desde la que rige, ordenados por fecha. El primero rige desde que se creó la categoría,
+        /// y los últimos pueden estar programados para más adelante.
+        precios: Vec<(Fecha, u128)>,
+        incluidas: Vec<IdActividad>,
+        elegibles: Vec<IdActividad>,
+        cantidad_a_elegir: u8,
+        maximo_a_elegir: u8,
+        precio_por_actividad_extra: u128,
     }
 
     impl Categoria {
-        /// Retorna el Índice de la categoría (0, 1 o 2 para A, B y C respectivamente) 
-        pub fn num(&self) -> usize {
-            use Categoria::*;
-            match self {
-                CategoriaA => 0,
-                CategoriaB(_) => 1,
-                CategoriaC => 2,
+        /// Crea una categoría sin actividades extra, con el precio dado desde la fecha dada.
+        fn new(nombre: String, precio: u128, incluidas: Vec<IdActividad>, elegibles: Vec<IdActividad>, cantidad_a_elegir: u8, desde: Fecha) -> Self {
+            Self {
+                nombre,
+                precios: ink::prelude::vec![(desde, precio)],
+                incluidas,
+                elegibles,
+                cantidad_a_elegir,
+                maximo_a_elegir: cantidad_a_elegir,
+                precio_por_actividad_extra: 0,
+            }
+        }
+        /// Retorna el nombre de la categoría.
+        pub fn get_nombre(&self) -> &str {
+            &self.nombre
+        }
+        /// Retorna los precios fijados a mano para la categoría, con la fecha desde la que rige cada uno, ordenados por fecha.
+        /// No incluye los ajustes de la indexación (ver [Categoria::get_historial_precios]).
+        pub fn get_precios_fijados(&self) -> &[(Fecha, u128)] {
+            &self.precios
+        }
+        /// Retorna el precio de la categoría vigente en la fecha dada, con los ajustes de la indexación dada si hay,
+        /// o None si no entra en un u128. Para fechas anteriores a la creación de la categoría, retorna el primer precio.
+        pub fn get_precio_en(&self, fecha: Fecha, indexacion: Option<&Indexacion>) -> Option<u128> {
+            let vigentes = self.precios_vigentes_hasta(fecha, indexacion)?;
+            Some(vigentes.last().unwrap_or(&self.precios[0]).1)
+        }
+        /// Retorna cada precio que tuvo la categoría hasta la fecha dada, contando los ajustes de la indexación dada si hay,
+        /// y los que tiene fijados para después, ordenados por fecha. Retorna None si algún ajuste no entra en un u128.
+        pub fn get_historial_precios(&self, hasta: Fecha, indexacion: Option<&Indexacion>) -> Option<Vec<(Fecha, u128)>> {
+            let mut historial = self.precios_vigentes_hasta(hasta, indexacion)?;
+            historial.extend(self.precios.iter().filter(|(desde, _)| desde.es_mayor(&hasta)));
+            Some(historial)
+        }
+        /// Retorna los precios fijados hasta la fecha dada, cada uno seguido de los ajustes de la indexación
+        /// que rigieron hasta el siguiente precio fijado, o None si algún ajuste no entra en un u128.
+        fn precios_vigentes_hasta(&self, hasta: Fecha, indexacion: Option<&Indexacion>) -> Option<Vec<(Fecha, u128)>> {
+            let mut vigentes = Vec::new();
+            for (i, &(desde, precio)) in self.precios.iter().enumerate() {
+                if desde.es_mayor(&hasta) {
+                    break;
+                }
+                vigentes.push((desde, precio));
+                let Some(indexacion) = indexacion else { continue };
+                let siguiente = self.precios.get(i + 1).map(|&(fecha, _)| fecha);
+                let mut precio = precio;
+                for ajuste in indexacion.ajustes_entre(desde, hasta) {
+                    // Un precio fijado a mano reemplaza a la indexación hasta el próximo ajuste
+                    if siguiente.is_some_and(|siguiente| !siguiente.es_mayor(&ajuste)) {
+                        break;
+                    }
+                    precio = indexacion.aplicar(precio)?;
+                    vigentes.push((ajuste, precio));
+                }
             }
+            Some(vigentes)
+        }
+        /// Reemplaza los precios fijados por el historial hasta la fecha dada con los ajustes de la indexación dada,
+        /// para que sigan constando cuando cambie la indexación. Retorna None si algún ajuste no entra en un u128.
+        fn fijar_ajustes(&mut self, hasta: Fecha, indexacion: Option<&Indexacion>) -> Option<()> {
+            self.precios = self.get_historial_precios(hasta, indexacion)?;
+            Some(())
         }
-        /// Retorna true si un socio de la categoría puede acceder a la actividad dada.
-        pub fn puede_acceder_a(&self, actividad: Actividad) -> bool {
-            use Categoria::*;
-            match self {
-                CategoriaA => true,
-                CategoriaB(act) => actividad == Actividad::Gimnasio || actividad == *act,
-                CategoriaC => actividad == Actividad::Gimnasio
+        /// Programa el precio desde la fecha dada, reemplazando el que hubiera desde esa misma fecha.
+        fn programar_precio(&mut self, desde: Fecha, precio: u128) {
+            match self.precios.iter().position(|(fecha, _)| !desde.es_mayor(fecha)) {
+                Some(i) if self.precios[i].0 == desde => self.precios[i].1 = precio,
+                Some(i) => self.precios.insert(i, (desde, precio)),
+                None => self.precios.push((desde, precio)),
             }
         }
-        /// Causa un panic si la infomarción de la categoría es inválida
-        pub fn assert_valida(&self) {
-            use Categoria::*;
-            assert_ne!(self, &CategoriaB(Actividad::Gimnasio), "El gimnasio está disponible para todos los socios; no corresponde a la elección en la categoría B.");
+        /// Retorna las actividades a las que pueden acceder todos los socios de la categoría.
+        pub fn get_incluidas(&self) -> &[IdActividad] {
+            &self.incluidas
+        }
+        /// Retorna las actividades entre las que eligen los socios de la categoría.
+        pub fn get_elegibles(&self) -> &[IdActividad] {
+            &self.elegibles
+        }
+        /// Retorna cuántas actividades elige cada socio de la categoría, incluidas en el precio.
+        pub fn get_cantidad_a_elegir(&self) -> u8 {
+            self.cantidad_a_elegir
+        }
+        /// Retorna cuántas actividades puede elegir como máximo cada socio de la categoría, contando las extra.
+        pub fn get_maximo_a_elegir(&self) -> u8 {
+            self.maximo_a_elegir
+        }
+        /// Retorna el precio de cada actividad extra.
+        pub fn get_precio_por_actividad_extra(&self) -> u128 {
+            self.precio_por_actividad_extra
+        }
+        /// Retorna lo que paga un socio de la categoría con las actividades elegidas dadas (que tienen que ser válidas)
+        /// por un período que vence en la fecha dada con la indexación dada, o None si no entra en un u128.
+        pub fn precio_con(&self, elegidas: &[IdActividad], vencimiento: Fecha, indexacion: Option<&Indexacion>) -> Option<u128> {
+            let extras = elegidas.len().saturating_sub(self.cantidad_a_elegir as usize) as u128;
+            self.precio_por_actividad_extra.checked_mul(extras)?.checked_add(self.get_precio_en(vencimiento, indexacion)?)
+        }
+        /// Retorna true si un socio de la categoría que eligió las actividades dadas puede acceder a la actividad dada.
+        pub fn puede_acceder_a(&self, elegidas: &[IdActividad], actividad: IdActividad) -> bool {
+            self.incluidas.contains(&actividad) ||
+            (elegidas.contains(&actividad) && self.elegibles.contains(&actividad))
+        }
+        /// Retorna [ClubError::CategoriaInvalida] si las actividades elegidas no son válidas para la categoría:
+        /// tienen que ser actividades elegibles distintas, entre [Categoria::get_cantidad_a_elegir] y
+        /// [Categoria::get_maximo_a_elegir].
+        pub fn validar_eleccion(&self, elegidas: &[IdActividad]) -> Result<(), ClubError> {
+            if elegidas.len() < self.cantidad_a_elegir as usize || elegidas.len() > self.maximo_a_elegir as usize {
+                return Err(ClubError::CategoriaInvalida);
+            }
+            for (i, actividad) in elegidas.iter().enumerate() {
+                if !self.elegibles.contains(actividad) || elegidas[..i].contains(actividad) {
+                    return Err(ClubError::CategoriaInvalida);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Los estados en los que puede estar un socio.
+    pub enum EstadoSocio {
+        /// Paga sus cuotas y puede acceder a las actividades de su categoría.
+        Activo,
+        /// No puede pagar ni acceder a las actividades, y su pago pendiente queda en espera.
+        Suspendido,
+        /// Ya no es socio: no tiene pagos pendientes ni se le generan nuevos, pero puede ser readmitido.
+        Baja,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// El recargo que se cobra a los pagos que se completan después del vencimiento.
+    pub enum Recargo {
+        /// No se cobra recargo.
+        Ninguno,
+        /// Se cobra un monto fijo, sin importar los días de atraso.
+        Fijo(u128),
+        /// Se cobra un porcentaje del monto del pago, sin importar los días de atraso.
+        Porcentaje(u8),
+        /// Se cobra un interés por cada día de atraso, en milésimas del monto del pago,
+        /// hasta un tope expresado como porcentaje del monto del pago.
+        InteresDiario {
+            por_mil_diario: u16,
+            tope_porcentaje: u16,
+        },
+    }
+
+    impl Recargo {
+        /// Retorna el recargo para un pago del monto dado con los días de atraso dados, o None si no entra en un u128.
+        pub fn calcular(&self, monto: u128, dias_de_atraso: u32) -> Option<u128> {
+            use Recargo::*;
+            if dias_de_atraso == 0 {
+                return Some(0);
+            }
+            match *self {
+                Ninguno => Some(0),
+                Fijo(recargo) => Some(recargo),
+                Porcentaje(porcentaje) => Some(monto.checked_mul(porcentaje as u128)? / 100),
+                InteresDiario { por_mil_diario, tope_porcentaje } => {
+                    let interes = monto.checked_mul(por_mil_diario as u128)?.checked_mul(dias_de_atraso as u128)? / 1000;
+                    let tope = monto.checked_mul(tope_porcentaje as u128)? / 100;
+                    Some(interes.min(tope))
+                }
+            }
+        }
+        /// Retorna [ClubError::ValorInvalido] si alguno de los valores del recargo es 0.
+        pub fn assert_valido(&self) -> Result<(), ClubError> {
+            use Recargo::*;
+            match *self {
+                Fijo(0) | Porcentaje(0) => Err(ClubError::ValorInvalido),
+                InteresDiario { por_mil_diario, tope_porcentaje } if por_mil_diario == 0 || tope_porcentaje == 0 => Err(ClubError::ValorInvalido),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Cómo se redondean los precios ajustados por la [Indexacion].
+    pub enum Redondeo {
+        /// Al múltiplo anterior.
+        Abajo,
+        /// Al múltiplo siguiente.
+        Arriba,
+        /// Al múltiplo más cercano, o al siguiente si está justo en el medio.
+        Cercano,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Un ajuste automático de los precios de las categorías: desde la fecha `desde` y cada `cada_meses` meses,
+    /// el precio de cada categoría aumenta `por_mil` por mil, redondeado a un múltiplo de `multiplo`.
+    /// Para ajustar cada mes calendario, se usa `cada_meses` 1 y `desde` el primer día de un mes.
+    /// 
+    /// Un precio fijado a mano reemplaza al ajustado, y los ajustes siguientes se aplican sobre él.
+    pub struct Indexacion {
+        por_mil: u16,
+        cada_meses: u8,
+        desde: Fecha,
+        multiplo: u128,
+        redondeo: Redondeo,
+    }
+
+    impl Indexacion {
+        /// Retorna cuánto aumenta el precio en cada ajuste, por mil.
+        pub fn get_por_mil(&self) -> u16 {
+            self.por_mil
+        }
+        /// Retorna cada cuántos meses se ajustan los precios.
+        pub fn get_cada_meses(&self) -> u8 {
+            self.cada_meses
+        }
+        /// Retorna la fecha del primer ajuste.
+        pub fn get_desde(&self) -> Fecha {
+            self.desde
+        }
+        /// Retorna el múltiplo al que se redondean los precios ajustados.
+        pub fn get_multiplo(&self) -> u128 {
+            self.multiplo
+        }
+        /// Retorna cómo se redondean los precios ajustados.
+        pub fn get_redondeo(&self) -> Redondeo {
+            self.redondeo
+        }
+        /// Retorna [ClubError::ValorInvalido] si el porcentaje, los meses o el múltiplo son 0.
+        pub fn assert_valida(&self) -> Result<(), ClubError> {
+            if self.por_mil == 0 || self.cada_meses == 0 || self.multiplo == 0 {
+                return Err(ClubError::ValorInvalido);
+            }
+            Ok(())
+        }
+        /// Retorna el precio dado con un ajuste aplicado, o None si no entra en un u128.
+        pub fn aplicar(&self, precio: u128) -> Option<u128> {
+            let unidad = self.multiplo.checked_mul(1000)?;
+            let ajustado = precio.checked_mul(1000 + self.por_mil as u128)?;
+            let (multiplos, resto) = (ajustado / unidad, ajustado % unidad);
+            let redondear_arriba = match self.redondeo {
+                Redondeo::Abajo => false,
+                Redondeo::Arriba => resto > 0,
+                Redondeo::Cercano => resto >= unidad - resto,
+            };
+            (multiplos + redondear_arriba as u128).checked_mul(self.multiplo)
+        }
+        /// Retorna las fechas de los ajustes posteriores a `desde` y hasta `hasta` inclusive.
+        fn ajustes_entre(&self, desde: Fecha, hasta: Fecha) -> Vec<Fecha> {
+            let mut ajustes = Vec::new();
+            let mut ajuste = self.desde;
+            let mut meses = 0;
+            while !ajuste.es_mayor(&hasta) {
+                if ajuste.es_mayor(&desde) {
+                    ajustes.push(ajuste);
+                }
+                // Se suma desde el primer ajuste para no perder el día al pasar por meses más cortos
+                meses += self.cada_meses as i32;
+                ajuste = self.desde;
+                ajuste.sumar_meses(meses);
+            }
+            ajustes
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// El descuento que hace un cupón sobre el monto de un pago.
+    pub enum Descuento {
+        /// Se descuenta un porcentaje del monto.
+        Porcentaje(u8),
+        /// Se descuenta un monto fijo, sin pasarse del monto del pago.
+        Fijo(u128),
+    }
+
+    impl Descuento {
+        /// Retorna cuánto se descuenta de un pago del monto dado.
+        pub fn calcular(&self, monto: u128) -> u128 {
+            match *self {
+                // Separado en dos partes para que no se desborde
+                Descuento::Porcentaje(porcentaje) => monto / 100 * porcentaje as u128 + monto % 100 * porcentaje as u128 / 100,
+                Descuento::Fijo(descuento) => descuento.min(monto),
+            }
+        }
+        /// Retorna [ClubError::ValorInvalido] si el descuento es 0, o si el porcentaje es mayor a 100.
+        pub fn assert_valido(&self) -> Result<(), ClubError> {
+            match *self {
+                Descuento::Porcentaje(0) | Descuento::Fijo(0) => Err(ClubError::ValorInvalido),
+                Descuento::Porcentaje(porcentaje) if porcentaje > 100 => Err(ClubError::ValorInvalido),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Un cupón de descuento, que se puede usar en los pagos de las categorías dadas (o de todas si no hay ninguna)
+    /// entre las fechas `desde` y `hasta`, hasta `usos_maximos` veces.
+    pub struct Cupon {
+        codigo: String,
+        descuento: Descuento,
+        desde: Fecha,
+        hasta: Fecha,
+        usos_maximos: u32,
+        usos: u32,
+        categorias: Vec<IdCategoria>,
+    }
+
+    impl Cupon {
+        /// Retorna el código del cupón.
+        pub fn get_codigo(&self) -> &str {
+            &self.codigo
+        }
+        /// Retorna el descuento del cupón.
+        pub fn get_descuento(&self) -> Descuento {
+            self.descuento
+        }
+        /// Retorna la primera fecha en la que se puede usar el cupón.
+        pub fn get_desde(&self) -> Fecha {
+            self.desde
+        }
+        /// Retorna la última fecha en la que se puede usar el cupón.
+        pub fn get_hasta(&self) -> Fecha {
+            self.hasta
+        }
+        /// Retorna cuántas veces se puede usar el cupón en total.
+        pub fn get_usos_maximos(&self) -> u32 {
+            self.usos_maximos
+        }
+        /// Retorna cuántas veces se usó el cupón.
+        pub fn get_usos(&self) -> u32 {
+            self.usos
+        }
+        /// Retorna las categorías en las que se puede usar el cupón. Si está vacío, se puede usar en todas.
+        pub fn get_categorias(&self) -> &[IdCategoria] {
+            &self.categorias
+        }
+        /// Retorna [ClubError::CuponNoAplicable] si el cupón no se puede usar en la fecha dada en un pago de la categoría dada:
+        /// porque la fecha está fuera de su vigencia, porque ya se usó todas las veces permitidas, o porque la categoría no es elegible.
+        pub fn verificar_aplicable(&self, categoria: IdCategoria, fecha: Fecha) -> Result<(), ClubError> {
+            if self.desde.es_mayor(&fecha) || fecha.es_mayor(&self.hasta) || self.usos >= self.usos_maximos
+                || (!self.categorias.is_empty() && !self.categorias.contains(&categoria)) {
+                return Err(ClubError::CuponNoAplicable);
+            }
+            Ok(())
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Cada cuánto vencen los pagos de los socios. Cada pago vence un ciclo después del anterior.
+    pub enum CicloFacturacion {
+        /// Cada la cantidad de días dada.
+        Dias(u16),
+        /// Cada la cantidad de meses calendario dada (1 es mensual, 3 trimestral, 12 anual).
+        /// Los pagos vencen el día del mes dado, o si es None, el mismo día del mes en que venció el primer pago del socio.
+        /// En los meses más cortos vencen el último día del mes.
+        Meses {
+            meses: u8,
+            dia: Option<i8>,
+        },
+    }
+
+    impl CicloFacturacion {
+        /// Retorna el vencimiento del pago que sigue a uno con el vencimiento dado.
+        /// `dia_del_socio` es el día del mes en el que vencen los pagos del socio, si el ciclo es por meses sin un día fijo.
+        pub fn siguiente_vencimiento(&self, vencimiento: Fecha, dia_del_socio: i8) -> Fecha {
+            let mut siguiente = vencimiento;
+            match *self {
+                CicloFacturacion::Dias(dias) => siguiente.sumar_dias(dias as i32),
+                CicloFacturacion::Meses { meses, dia } => {
+                    siguiente.sumar_meses(meses as i32);
+                    siguiente.ajustar_dia(dia.unwrap_or(dia_del_socio));
+                }
+            }
+            siguiente
+        }
+        /// Retorna la duración en días del ciclo que termina en la fecha dada (siempre al menos 1).
+        pub fn dias_antes_de(&self, vencimiento: Fecha) -> i32 {
+            match *self {
+                CicloFacturacion::Dias(dias) => dias as i32,
+                CicloFacturacion::Meses { meses, .. } => {
+                    let mut inicio = vencimiento;
+                    inicio.sumar_meses(-(meses as i32));
+                    inicio.dias_hasta(&vencimiento)
+                }
+            }.max(1)
+        }
+        /// Retorna [ClubError::ValorInvalido] si el ciclo es de 0 días o meses, o el día fijo no está entre 1 y 31.
+        pub fn assert_valido(&self) -> Result<(), ClubError> {
+            match *self {
+                CicloFacturacion::Dias(0) | CicloFacturacion::Meses { meses: 0, .. } => Err(ClubError::ValorInvalido),
+                CicloFacturacion::Meses { dia: Some(dia), .. } if !(1..=31).contains(&dia) => Err(ClubError::ValorInvalido),
+                _ => Ok(()),
+            }
         }
     }
 
@@ -394,13 +2870,31 @@ pub mod trabajo_final {
         derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
     )]
     /// Información sobre un socio del club
-    /// Contiene el dni, nombre, la categoría elegida (incluida la actividad si elegió la B),
-    /// y la cantidad de meses consecutivos que el socio lleva pagando a tiempo, la cual vuelve a 0 al conseguir el descuento. 
+    /// Contiene el dni, nombre, la categoría elegida con las actividades que eligió en ella,
+    /// la cantidad de meses consecutivos que el socio lleva pagando a tiempo, la cual vuelve a 0 al conseguir el descuento
+    /// o al darse de baja,
+    /// la cuenta del socio, si la tiene vinculada, y su estado.
+    /// Si es parte de un grupo familiar, el titular guarda a sus dependientes y cada dependiente a su titular.
     pub struct Socio {
         dni:u128,
         nombre: String,
-        categoria: Categoria,
+        categoria: IdCategoria,
+        actividades_elegidas: Vec<IdActividad>,
         pagos_a_tiempo_consecutivos: u16,
+        cuenta: Option<AccountId>,
+        estado: EstadoSocio,
+        /// Día del mes en el que vencen sus pagos, si el ciclo de facturación es por meses sin un día fijo.
+        dia_de_vencimiento: i8,
+        /// Id del titular del grupo familiar del que es dependiente.
+        titular: Option<u64>,
+        /// Ids de los dependientes de su grupo familiar, si es titular.
+        dependientes: Vec<u64>,
+        /// Posición en su historial de pagos del primer pago que se le generó al empezar a pagar por su cuenta por última vez.
+        primer_pago: u32,
+        /// Saldo a favor que se descuenta de los próximos pagos que se le generen.
+        saldo_a_favor: u128,
+        /// Fecha en la que se hizo socio, que puede ser anterior a su registro si se importó de otro sistema.
+        fecha_alta: Fecha,
     }
     impl Socio {
         /// Retorna el DNI del socio.
@@ -412,9 +2906,58 @@ pub mod trabajo_final {
             &self.nombre
         }
         /// Retorna la categoria seleccionada por el socio.
-        pub fn get_categoria(&self) -> Categoria {
+        pub fn get_categoria(&self) -> IdCategoria {
             self.categoria
         }
+        /// Retorna las actividades que eligió el socio en su categoría.
+        pub fn get_actividades_elegidas(&self) -> &[IdActividad] {
+            &self.actividades_elegidas
+        }
+        /// Retorna la cuenta vinculada con el socio, si tiene una.
+        pub fn get_cuenta(&self) -> Option<AccountId> {
+            self.cuenta
+        }
+        /// Retorna el estado del socio.
+        pub fn get_estado(&self) -> EstadoSocio {
+            self.estado
+        }
+        /// Retorna true si el socio está activo.
+        pub fn es_activo(&self) -> bool {
+            self.estado == EstadoSocio::Activo
+        }
+        /// Retorna el id del titular del grupo familiar del socio, si es dependiente.
+        pub fn get_titular(&self) -> Option<u64> {
+            self.titular
+        }
+        /// Retorna los ids de los dependientes del socio, si es titular de un grupo familiar.
+        pub fn get_dependientes(&self) -> &[u64] {
+            &self.dependientes
+        }
+        /// Retorna el saldo a favor del socio.
+        pub fn get_saldo_a_favor(&self) -> u128 {
+            self.saldo_a_favor
+        }
+        /// Retorna la fecha en la que se hizo socio.
+        pub fn get_fecha_alta(&self) -> Fecha {
+            self.fecha_alta
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Default, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Un socio a registrar con [Club::importar_socios].
+    pub struct SocioAImportar {
+        pub dni: u128,
+        pub nombre: String,
+        pub categoria: IdCategoria,
+        pub actividades_elegidas: Vec<IdActividad>,
+        /// Fecha en la que se hizo socio, o None si es hoy.
+        pub alta: Option<Fecha>,
+        /// Lo que adeudaba en el sistema anterior, que se suma a su primer pago.
+        pub deuda: Option<u128>,
     }
 
     #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq, Eq)]
@@ -429,6 +2972,26 @@ pub mod trabajo_final {
         vencimiento: Fecha,
         pagado: Option<Fecha>,
         es_descuento: bool,
+        cancelado: bool,
+        es_adelantado: bool,
+        /// Fecha y monto de cada pago parcial, incluido el que completó el pago.
+        pagos_parciales: Vec<(Fecha, u128)>,
+        /// Recargo cobrado por completar el pago después del vencimiento.
+        recargo: u128,
+        categoria: IdCategoria,
+        categoria_anterior: Option<IdCategoria>,
+        /// Código del cupón usado en el pago.
+        cupon: Option<String>,
+        /// Monto que se descontó con el cupón (ya restado del monto).
+        descuento_cupon: u128,
+        /// Cuenta y monto de cada parte del pago que se pagó con tokens, para poder devolverlos.
+        pagos_con_tokens: Vec<(AccountId, Balance)>,
+        /// Pagos a tiempo consecutivos que tenía el socio antes de este pago, para poder revertirlo.
+        pagos_a_tiempo_previos: u16,
+        /// Fecha en la que se revirtió el pago y cuenta que lo revirtió.
+        revertido: Option<(Fecha, AccountId)>,
+        /// Saldo a favor del socio que se usó al generar el pago (ya restado del monto).
+        saldo_aplicado: u128,
     }
 
     impl Pago {
@@ -447,7 +3010,7 @@ pub mod trabajo_final {
             self.vencimiento
         }
 
-        /// Retorna un [Option] con la fecha de pago o None si aun no fue pagado.
+        /// Retorna un [Option] con la fecha en la que se completó el pago o None si aun no fue pagado por completo.
         pub fn get_pagado(&self) -> Option<Fecha> {
             self.pagado
         }
@@ -457,36 +3020,280 @@ pub mod trabajo_final {
             self.es_descuento
         }
 
-        /// Retorna true si fue pagado, o false en caso contrario.
-        pub fn es_pagado(&self) -> bool {
-            self.pagado.is_some()
-        }
+        /// Retorna la categoría en la que se cobra el pago.
+        pub fn get_categoria(&self) -> IdCategoria {
+            self.categoria
+        }
+
+        /// Retorna la categoría en la que estaba el socio si la cambió mientras el pago estaba pendiente.
+        pub fn get_categoria_anterior(&self) -> Option<IdCategoria> {
+            self.categoria_anterior
+        }
+
+        /// Retorna true si el pago se canceló porque el socio se dio de baja antes de pagarlo.
+        pub fn es_cancelado(&self) -> bool {
+            self.cancelado
+        }
+
+        /// Retorna la fecha y el monto de cada pago parcial. Si el pago se completó en partes, incluye la última.
+        pub fn get_pagos_parciales(&self) -> &[(Fecha, u128)] {
+            &self.pagos_parciales
+        }
+
+        /// Retorna cuánto se pagó hasta ahora: el monto completo con el recargo si ya fue pagado,
+        /// o la suma de los pagos parciales si no.
+        pub fn get_monto_pagado(&self) -> u128 {
+            if self.es_pagado() {
+                return self.monto.saturating_add(self.recargo);
+            }
+            self.pagos_parciales.iter().map(|(_, monto)| monto).sum()
+        }
+
+        /// Retorna cuánto falta pagar para completar el pago, sin contar el recargo que se le pueda cobrar si está atrasado.
+        pub fn get_monto_restante(&self) -> u128 {
+            self.monto.saturating_add(self.recargo).saturating_sub(self.get_monto_pagado())
+        }
+
+        /// Retorna el recargo que se cobró por completar el pago después del vencimiento.
+        pub fn get_recargo(&self) -> u128 {
+            self.recargo
+        }
+
+        /// Retorna el código del cupón de descuento usado en el pago, si tiene uno.
+        pub fn get_cupon(&self) -> Option<&str> {
+            self.cupon.as_deref()
+        }
+
+        /// Retorna el monto que se descontó con el cupón, que ya está restado del monto del pago.
+        pub fn get_descuento_cupon(&self) -> u128 {
+            self.descuento_cupon
+        }
+
+        /// Retorna la cuenta y el monto de cada parte del pago que se pagó con tokens.
+        pub fn get_pagos_con_tokens(&self) -> &[(AccountId, Balance)] {
+            &self.pagos_con_tokens
+        }
+
+        /// Retorna la fecha en la que se revirtió el pago y la cuenta que lo revirtió, si fue revertido.
+        pub fn get_revertido(&self) -> Option<(Fecha, AccountId)> {
+            self.revertido
+        }
+
+        /// Retorna el saldo a favor del socio que se usó en el pago, que ya está restado del monto.
+        pub fn get_saldo_aplicado(&self) -> u128 {
+            self.saldo_aplicado
+        }
+
+        /// Retorna true si el pago fue revertido (ver [Club::revertir_pago]).
+        pub fn es_revertido(&self) -> bool {
+            self.revertido.is_some()
+        }
+
+        /// Retorna true si el pago se pagó por adelantado, junto con los anteriores.
+        pub fn get_es_adelantado(&self) -> bool {
+            self.es_adelantado
+        }
+
+        /// Retorna true si fue pagado, o false en caso contrario.
+        pub fn es_pagado(&self) -> bool {
+            self.pagado.is_some()
+        }
+
+        /// Retorna un [Option] con true si se completó el pago antes o en la fecha de vencimiento,
+        /// false en caso contrario, y None si aún no se completó.
+        pub fn es_pagado_a_tiempo(&self) -> Option<bool> {
+            // no es mayor = es menor o igual = se pagó en el día de vencimiento o antes
+            self.pagado.map(|fecha_pagado| !fecha_pagado.es_mayor(&self.vencimiento))
+        }
+
+        /// Devuelve true si, a la fecha ingresada, el pago está pendiente y ya pasó la fecha de vencimiento.
+        /// Los pagos parciales no alcanzan: el pago es moroso hasta que se completa. Los pagos cancelados nunca son morosos.
+        pub fn es_moroso(&self, fecha_actual: Fecha) -> bool {
+            if self.es_pagado() || self.cancelado {return false;}
+            fecha_actual.es_mayor(&self.vencimiento)
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Default, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Filtros para buscar socios de a páginas (ver [Club::get_socios_pagina]). Los que están en None no filtran nada.
+    pub struct FiltroSocios {
+        /// Sólo los socios de esta categoría.
+        pub categoria: Option<IdCategoria>,
+        /// Sólo los socios que pueden acceder a esta actividad.
+        pub actividad: Option<IdActividad>,
+        /// Sólo los socios en este estado.
+        pub estado: Option<EstadoSocio>,
+    }
+
+    impl FiltroSocios {
+        /// Retorna true si el socio dado, que es de la categoría dada, cumple el filtro.
+        pub fn acepta(&self, socio: &Socio, categoria: &Categoria) -> bool {
+            self.categoria.is_none_or(|id| socio.categoria == id)
+                && self.actividad.is_none_or(|actividad| categoria.puede_acceder_a(&socio.actividades_elegidas, actividad))
+                && self.estado.is_none_or(|estado| socio.estado == estado)
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Default, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Filtros para buscar pagos de a páginas (ver [Club::get_pagos_pagina]). Los que están en None no filtran nada,
+    /// y las fechas de los rangos están incluidas.
+    pub struct FiltroPagos {
+        /// Sólo los pagos del socio con este dni.
+        pub socio: Option<u128>,
+        /// Sólo los pagos que se cobran en esta categoría.
+        pub categoria: Option<IdCategoria>,
+        /// Sólo los pagos completados (true), o los que faltan completar y no se cancelaron (false).
+        pub pagado: Option<bool>,
+        /// Sólo los pagos que vencen desde esta fecha.
+        pub vencimiento_desde: Option<Fecha>,
+        /// Sólo los pagos que vencen hasta esta fecha.
+        pub vencimiento_hasta: Option<Fecha>,
+        /// Sólo los pagos completados desde esta fecha.
+        pub pagado_desde: Option<Fecha>,
+        /// Sólo los pagos completados hasta esta fecha.
+        pub pagado_hasta: Option<Fecha>,
+    }
+
+    impl FiltroPagos {
+        /// Retorna true si el pago dado cumple el filtro, sin contar el filtro por socio.
+        pub fn acepta(&self, pago: &Pago) -> bool {
+            let entre = |fecha: Fecha, desde: Option<Fecha>, hasta: Option<Fecha>| {
+                desde.is_none_or(|desde| !desde.es_mayor(&fecha)) && hasta.is_none_or(|hasta| !fecha.es_mayor(&hasta))
+            };
+            let filtra_pagado = self.pagado_desde.is_some() || self.pagado_hasta.is_some();
+            self.categoria.is_none_or(|categoria| pago.categoria == categoria)
+                && self.pagado.is_none_or(|pagado| if pagado { pago.es_pagado() } else { !pago.es_pagado() && !pago.cancelado })
+                && entre(pago.vencimiento, self.vencimiento_desde, self.vencimiento_hasta)
+                && match pago.pagado {
+                    Some(fecha) => entre(fecha, self.pagado_desde, self.pagado_hasta),
+                    None => !filtra_pagado,
+                }
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Un período que un socio todavía no terminó de pagar, en su estado de cuenta (ver [Club::estado_de_cuenta]).
+    pub struct PeriodoAdeudado {
+        vencimiento: Fecha,
+        categoria: IdCategoria,
+        /// Monto del pago, con los descuentos ya restados.
+        monto: u128,
+        /// Lo que ya se pagó con pagos parciales.
+        pagado: u128,
+        dias_de_atraso: u32,
+        /// Recargo que se cobraría si se completara hoy.
+        recargo: u128,
+        es_bonificado: bool,
+        descuento_cupon: u128,
+        saldo_aplicado: u128,
+    }
+
+    impl PeriodoAdeudado {
+        /// Retorna el vencimiento del pago del período.
+        pub fn get_vencimiento(&self) -> Fecha {
+            self.vencimiento
+        }
+        /// Retorna la categoría en la que se cobra el período.
+        pub fn get_categoria(&self) -> IdCategoria {
+            self.categoria
+        }
+        /// Retorna el monto del pago, con los descuentos ya restados y sin el recargo.
+        pub fn get_monto(&self) -> u128 {
+            self.monto
+        }
+        /// Retorna lo que ya se pagó del período con pagos parciales.
+        pub fn get_pagado(&self) -> u128 {
+            self.pagado
+        }
+        /// Retorna los días que pasaron desde el vencimiento, o 0 si todavía no venció.
+        pub fn get_dias_de_atraso(&self) -> u32 {
+            self.dias_de_atraso
+        }
+        /// Retorna el recargo que se cobraría si el pago se completara hoy.
+        pub fn get_recargo(&self) -> u128 {
+            self.recargo
+        }
+        /// Retorna true si el pago tiene la bonificación por pagos a tiempo consecutivos.
+        pub fn es_bonificado(&self) -> bool {
+            self.es_bonificado
+        }
+        /// Retorna lo que se descontó con un cupón.
+        pub fn get_descuento_cupon(&self) -> u128 {
+            self.descuento_cupon
+        }
+        /// Retorna el saldo a favor que se usó en el pago.
+        pub fn get_saldo_aplicado(&self) -> u128 {
+            self.saldo_aplicado
+        }
+        /// Retorna lo que falta pagar hoy para completar el período, con el recargo.
+        pub fn get_deuda(&self) -> u128 {
+            self.monto.saturating_add(self.recargo).saturating_sub(self.pagado)
+        }
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    /// Lo que adeuda un socio (o su grupo familiar) a la fecha (ver [Club::estado_de_cuenta]).
+    pub struct EstadoDeCuenta {
+        /// Dni del socio que paga, que es el titular si el socio es dependiente.
+        dni: u128,
+        /// Los períodos adeudados, del más antiguo al más reciente.
+        periodos: Vec<PeriodoAdeudado>,
+        saldo_a_favor: u128,
+        deuda_total: u128,
+    }
 
-        /// Retorna un [Option] con true si se realizó el pago antes o en la fecha de vencimiento,
-        /// false en caso contrario, y None si aún no se realizó.
-        pub fn es_pagado_a_tiempo(&self) -> Option<bool> {
-            // no es mayor = es menor o igual = se pagó en el día de vencimiento o antes
-            self.pagado.map(|fecha_pagado| !fecha_pagado.es_mayor(&self.vencimiento))
+    impl EstadoDeCuenta {
+        /// Retorna el dni del socio que paga, que es el titular si el socio es dependiente de un grupo familiar.
+        pub fn get_dni(&self) -> u128 {
+            self.dni
         }
-
-        /// Devuelve true si, a la fecha ingresada, el pago está pendiente y ya pasó la fecha de vencimiento.
-        pub fn es_moroso(&self, fecha_actual: Fecha) -> bool {
-            if self.es_pagado() {return false;}
-            fecha_actual.es_mayor(&self.vencimiento)
+        /// Retorna los períodos adeudados, del más antiguo al más reciente. El primero es el que se paga primero.
+        pub fn get_periodos(&self) -> &[PeriodoAdeudado] {
+            &self.periodos
+        }
+        /// Retorna el saldo a favor del socio, que se descuenta de los próximos pagos que se generen y no de la deuda.
+        pub fn get_saldo_a_favor(&self) -> u128 {
+            self.saldo_a_favor
+        }
+        /// Retorna lo que falta pagar hoy de todos los períodos adeudados, con los recargos.
+        pub fn get_deuda_total(&self) -> u128 {
+            self.deuda_total
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::panic;
-
     use crate::trabajo_final::*;
-    use Categoria::*;
-    use Actividad::*;
+    use crate::fecha::Fecha;
     use ink::codegen::{StaticEnv, Env};
     use ink_env::{DefaultEnvironment, Environment};
 
+    // Las categorías y actividades con las que empieza el club
+    const CATEGORIA_A: IdCategoria = 0;
+    const CATEGORIA_B: IdCategoria = 1;
+    const CATEGORIA_C: IdCategoria = 2;
+    const GIMNASIO: IdActividad = 0;
+    const FUTBOL: IdActividad = 1;
+    const TENIS: IdActividad = 6;
+    const PADDLE: IdActividad = 7;
+
     type TipoCuenta = <DefaultEnvironment as Environment>::AccountId;
     fn cuentas() -> ink_env::test::DefaultAccounts<DefaultEnvironment> {
         ink_env::test::default_accounts::<DefaultEnvironment>()
@@ -511,18 +3318,23 @@ mod tests {
 
     fn generar_club() -> Club {
         let mut club = Club::new(alicia());
-        club.set_politica_autorizacion(false);
+        club.set_politica_autorizacion(false).unwrap();
         club
     }
 
+    fn avanzar_dias(dias: u64) {
+        let tiempo = ink_env::block_timestamp::<DefaultEnvironment>() + dias * 24 * 60 * 60 * 1000;
+        ink_env::test::set_block_timestamp::<DefaultEnvironment>(tiempo);
+    }
+
     #[ink::test]
     fn valores_default_test() {
         let club = generar_club();
-        assert_eq!(club.get_precio(CategoriaA), 5000);
-        assert_eq!(club.get_precio(CategoriaB(Tenis)), 3000);
-        assert_eq!(club.get_precio(CategoriaC), 2000);
+        assert_eq!(club.get_precio(CATEGORIA_A).unwrap(), 5000);
+        assert_eq!(club.get_precio(CATEGORIA_B).unwrap(), 3000);
+        assert_eq!(club.get_precio(CATEGORIA_C).unwrap(), 2000);
         assert_eq!(club.get_nombre(), "Seminario Rust");
-        assert_eq!(club.get_pagos(None).len(), 0);
+        assert_eq!(club.get_pagos(None).unwrap().len(), 0);
         assert_eq!(club.get_cantidad_pagos_bonificacion(), 5);
         assert_eq!(club.get_porcentaje_bonificacion_pagos_consecutivos(), 10);
     }
@@ -530,84 +3342,102 @@ mod tests {
     #[ink::test]
     fn registrar_socio_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]), Ok(()));
     }
 
     #[ink::test]
-    #[should_panic]
     fn socio_inexistente_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
-        club.realizar_pago(1, 100000);
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(club.realizar_pago(1, 100000), Err(ClubError::SocioInexistente));
+        assert_eq!(club.get_pagos(Some(1)), Err(ClubError::SocioInexistente));
     }
 
-    #[should_panic]
     #[ink::test]
     fn registrar_socio_repetido_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]), Err(ClubError::SocioYaRegistrado));
     }
 
-    #[should_panic]
     #[ink::test]
     fn registrar_socio_categoria_invalida_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaB(Actividad::Gimnasio));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![GIMNASIO]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.get_socios().unwrap().len(), 0);
     }
 
     #[ink::test]
     fn realizar_pagos_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(5, "".into(), Categoria::CategoriaA);
+        club.registrar_nuevo_socio(5, "".into(), CATEGORIA_A, vec![]).unwrap();
         // Error: no existe cliente
-        let res = panic::catch_unwind(|| {
-            club.clone().realizar_pago(4, u128::MAX);
-        });
-        assert!(res.is_err());
+        assert_eq!(club.realizar_pago(4, u128::MAX), Err(ClubError::SocioInexistente));
         // Error: monto insuficiente
-        let res = panic::catch_unwind(|| {
-            club.clone().realizar_pago(5, club.get_precio(CategoriaA) - 1);
-        });
-        assert!(res.is_err());
+        assert_eq!(club.realizar_pago(5, club.get_precio(CATEGORIA_A).unwrap() - 1), Err(ClubError::MontoIncorrecto));
         // Funciona
-        club.realizar_pago(5, club.get_precio(CategoriaA));
+        assert_eq!(club.realizar_pago(5, club.get_precio(CATEGORIA_A).unwrap()), Ok(()));
     }
 
     #[ink::test]
     fn obtener_pagos_test() {
         let mut club = generar_club();
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
-        club.registrar_nuevo_socio(1, "".into(), Categoria::CategoriaB(Tenis));
-        club.registrar_nuevo_socio(2, "".into(), Categoria::CategoriaC);
-        club.realizar_pago(2, club.get_precio(CategoriaC));
-        assert_eq!(club.get_pagos(None).len(), 4);
-        assert_eq!(club.get_pagos(Some(0)).len(), 1);
-        assert_eq!(club.get_pagos(Some(1)).len(), 1);
-        assert_eq!(club.get_pagos(Some(2)).len(), 2);
-        assert_eq!(club.get_pagos(Some(0))[0].get_monto(), club.get_precio(CategoriaA));
-        assert_eq!(club.get_pagos(Some(1))[0].get_monto(), club.get_precio(CategoriaB(Tenis)));
-        assert_eq!(club.get_pagos(Some(2))[0].get_monto(), club.get_precio(CategoriaC));
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(2, club.get_precio(CATEGORIA_C).unwrap()).unwrap();
+        assert_eq!(club.get_pagos(None).unwrap().len(), 4);
+        assert_eq!(club.get_pagos(Some(0)).unwrap().len(), 1);
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 1);
+        assert_eq!(club.get_pagos(Some(2)).unwrap().len(), 2);
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[0].get_monto(), club.get_precio(CATEGORIA_A).unwrap());
+        assert_eq!(club.get_pagos(Some(1)).unwrap()[0].get_monto(), club.get_precio(CATEGORIA_B).unwrap());
+        assert_eq!(club.get_pagos(Some(2)).unwrap()[0].get_monto(), club.get_precio(CATEGORIA_C).unwrap());
     }
 
 
+    #[ink::test]
+    fn pagos_intercalados_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(10, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.realizar_pago(10, club.get_precio(CATEGORIA_A).unwrap()).unwrap();
+        club.registrar_nuevo_socio(20, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(10, club.get_precio(CATEGORIA_A).unwrap()).unwrap();
+        club.realizar_pago(20, club.get_precio(CATEGORIA_C).unwrap()).unwrap();
+        // Los pagos de cada socio quedan en orden, aunque estén intercalados con los de otros
+        let pagos_a = club.get_pagos(Some(10)).unwrap();
+        let pagos_c = club.get_pagos(Some(20)).unwrap();
+        assert_eq!(pagos_a.len(), 3);
+        assert_eq!(pagos_c.len(), 2);
+        assert!(pagos_a.iter().all(|p| p.get_socio() == 0 && p.get_monto() == club.get_precio(CATEGORIA_A).unwrap()));
+        assert!(pagos_c.iter().all(|p| p.get_socio() == 1 && p.get_monto() == club.get_precio(CATEGORIA_C).unwrap()));
+        assert!(pagos_a[0].es_pagado() && pagos_a[1].es_pagado() && !pagos_a[2].es_pagado());
+        assert!(pagos_a[1].get_vencimiento().es_mayor(&pagos_a[0].get_vencimiento()));
+        // Todos los pagos, en el orden en que se generaron
+        let socios_de_pagos: Vec<u64> = club.get_pagos(None).unwrap().iter().map(|p| p.get_socio()).collect();
+        assert_eq!(socios_de_pagos, vec![0, 0, 1, 0, 1]);
+        assert_eq!(club.get_socio(1).unwrap().get_dni(), 20);
+        assert_eq!(club.get_socio(2), Err(ClubError::SocioInexistente));
+        assert_eq!(club.get_socios().unwrap().len(), 2);
+    }
+
     #[ink::test]
     fn bonificacion_test() {
         let mut club = generar_club();
-        club.set_cantidad_pagos_bonificacion(2);
-        club.set_porcentaje_bonificacion_pagos_consecutivos(25);
-        club.set_precio(CategoriaA, 100);
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 75);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 75);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 100);
-        club.realizar_pago(0, 75);
-        let pagos = club.get_pagos(None);
+        club.set_cantidad_pagos_bonificacion(2).unwrap();
+        club.set_porcentaje_bonificacion_pagos_consecutivos(25).unwrap();
+        club.set_precio(CATEGORIA_A, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 75).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 75).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 75).unwrap();
+        let pagos = club.get_pagos(None).unwrap();
         assert_eq!(pagos[0].get_monto(), 100);
         assert_eq!(pagos[1].get_monto(), 100);
         assert_eq!(pagos[2].get_monto(), 75);
@@ -619,96 +3449,86 @@ mod tests {
     #[ink::test]
     fn pagos_exactos_test() {
         let mut club = generar_club();
-        club.set_cantidad_pagos_bonificacion(2);
-        club.set_porcentaje_bonificacion_pagos_consecutivos(25);
-        club.set_precio(CategoriaA, 100);
-        club.registrar_nuevo_socio(0, "".into(), Categoria::CategoriaA);
-        club.realizar_pago(0, 100);
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 101);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 99);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 75);
-        }).is_err());
-        club.realizar_pago(0, 100);
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 76);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 74);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, 100);
-        }).is_err());
-        club.realizar_pago(0, 75);
+        club.set_cantidad_pagos_bonificacion(2).unwrap();
+        club.set_porcentaje_bonificacion_pagos_consecutivos(25).unwrap();
+        club.set_precio(CATEGORIA_A, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        assert_eq!(club.realizar_pago(0, 101), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.realizar_pago(0, 99), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.realizar_pago(0, 75), Err(ClubError::MontoIncorrecto));
+        club.realizar_pago(0, 100).unwrap();
+        assert_eq!(club.realizar_pago(0, 76), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.realizar_pago(0, 74), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.realizar_pago(0, 100), Err(ClubError::MontoIncorrecto));
+        club.realizar_pago(0, 75).unwrap();
     }
 
     #[ink::test]
     fn autorizacion_test() {
         let mut club = generar_club();
-        club.set_politica_autorizacion(true);
+        club.set_politica_autorizacion(true).unwrap();
         ser_alicia();
         assert!(club.soy_el_dueño());
-        assert!(club.estoy_autorizado());
+        assert!(club.tengo_rol(Rol::Tesorero));
         ser_bob();
         assert!(!club.soy_el_dueño());
-        assert!(!club.estoy_autorizado());
-        // Bob no puede agregar autorizados
-        assert!(panic::catch_unwind(|| {
-            club.clone().agregar_autorizado(bob());
-        }).is_err());
+        assert!(!club.tengo_rol(Rol::Tesorero));
+        // Bob no puede otorgar roles
+        assert_eq!(club.otorgar_rol(bob(), Rol::Tesorero), Err(ClubError::NoEsDueño));
 
         ser_alicia();
-        club.agregar_autorizado(bob());
-        club.agregar_autorizado(carlos());
-        // doble autorización no es posible
-        assert!(panic::catch_unwind(|| {
-            club.clone().agregar_autorizado(bob());
-        }).is_err());
+        club.otorgar_rol(bob(), Rol::Tesorero).unwrap();
+        club.otorgar_rol(bob(), Rol::Secretaria).unwrap();
+        club.otorgar_rol(carlos(), Rol::Tesorero).unwrap();
+        // otorgar dos veces el mismo rol no es posible
+        assert_eq!(club.otorgar_rol(bob(), Rol::Tesorero), Err(ClubError::RolYaOtorgado));
+        assert_eq!(club.get_roles(bob()), vec![Rol::Tesorero, Rol::Secretaria]);
+        assert_eq!(club.get_cuentas_con_rol(Rol::Tesorero), vec![bob(), carlos()]);
         ser_bob();
-        assert!(club.estoy_autorizado());
+        assert!(club.tengo_rol(Rol::Tesorero));
+        assert!(club.tengo_rol(Rol::Secretaria));
+        assert!(!club.tengo_rol(Rol::Administrador));
         ser_alicia();
-        club.quitar_autorizado(bob());
+        club.revocar_rol(bob(), Rol::Tesorero).unwrap();
+        assert_eq!(club.revocar_rol(bob(), Rol::Tesorero), Err(ClubError::RolNoOtorgado));
         ser_bob();
-        assert!(!club.estoy_autorizado());
+        assert!(!club.tengo_rol(Rol::Tesorero));
+        assert!(club.tengo_rol(Rol::Secretaria));
+        ser_carlos();
+        assert!(club.tengo_rol(Rol::Tesorero));
+        // los que tienen roles no pueden otorgar más roles
+        assert_eq!(club.otorgar_rol(bob(), Rol::Tesorero), Err(ClubError::NoEsDueño));
+        assert_eq!(club.proponer_dueño(carlos()), Err(ClubError::NoEsDueño));
+        ser_alicia();
+        club.proponer_dueño(carlos()).unwrap();
         ser_carlos();
-        assert!(club.estoy_autorizado());
-        // los autorizados no pueden agregar más autorizados
-        assert!(panic::catch_unwind(|| {
-            club.clone().agregar_autorizado(bob());
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().cambiar_dueño(carlos());
-        }).is_err());
+        club.aceptar_dueño().unwrap();
         ser_alicia();
-        club.cambiar_dueño(carlos());
         assert!(!club.soy_el_dueño());
-        assert!(!club.estoy_autorizado());
+        assert!(!club.tengo_rol(Rol::Tesorero));
         ser_carlos();
         assert!(club.soy_el_dueño());
     }
 
     #[ink::test]
     fn no_autorizacion_test() {
-        // parecido al anterior, pero siempre están autorizados
+        // parecido al anterior, pero todos tienen todos los roles
         let mut club = generar_club();
-        club.set_politica_autorizacion(false);
+        club.set_politica_autorizacion(false).unwrap();
         ser_alicia();
         assert!(club.soy_el_dueño());
-        assert!(club.estoy_autorizado());
+        assert!(club.tengo_rol(Rol::Administrador));
         ser_bob();
         assert!(!club.soy_el_dueño());
-        assert!(club.estoy_autorizado());
-        club.agregar_autorizado(bob());
-        club.agregar_autorizado(carlos());
-        assert!(club.estoy_autorizado());
-        club.quitar_autorizado(bob());
-        assert!(club.estoy_autorizado());
+        assert!(club.tengo_rol(Rol::Administrador));
+        club.otorgar_rol(bob(), Rol::Tesorero).unwrap();
+        club.otorgar_rol(carlos(), Rol::Tesorero).unwrap();
+        assert!(club.tengo_rol(Rol::Auditor));
+        club.revocar_rol(bob(), Rol::Tesorero).unwrap();
+        assert!(club.tengo_rol(Rol::Tesorero));
         ser_carlos();
-        assert!(club.estoy_autorizado());
+        assert!(club.tengo_rol(Rol::Secretaria));
     }
 
 
@@ -716,69 +3536,1262 @@ mod tests {
     fn autorizacion_leer_cosas_test() {
         let mut club = generar_club();
         ser_alicia();
-        club.set_politica_autorizacion(true);
-        club.agregar_autorizado(carlos());
-        club.registrar_nuevo_socio(0, "Alicia".to_string(), CategoriaC);
-        club.realizar_pago(0, 2000);
-        club.realizar_pago(0, 2000);
-        club.realizar_pago(0, 2000);
+        club.set_politica_autorizacion(true).unwrap();
+        club.otorgar_rol(carlos(), Rol::Auditor).unwrap();
+        club.registrar_nuevo_socio(0, "Alicia".to_string(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(0, 2000).unwrap();
+        club.realizar_pago(0, 2000).unwrap();
+        club.realizar_pago(0, 2000).unwrap();
         ser_bob();
-        // Incluso sin autorización, todas estas cosas se deberían poder leer
-        assert_eq!(club.get_autorizados(), vec![carlos()]);
+        // Incluso sin roles, todas estas cosas se deberían poder leer
+        assert_eq!(club.get_roles(carlos()), vec![Rol::Auditor]);
+        assert_eq!(club.get_cuentas_con_rol(Rol::Auditor), vec![carlos()]);
         assert_eq!(club.get_cantidad_pagos_bonificacion(), 5);
         assert_eq!(club.get_porcentaje_bonificacion_pagos_consecutivos(), 10);
         assert_eq!(club.get_dueño(), alicia());
         assert_eq!(club.get_nombre(), "Seminario Rust");
-        assert_eq!(club.get_precio(CategoriaA), 5000);
-        assert_eq!(club.get_precio(CategoriaB(Paddle)), 3000);
-        assert_eq!(club.get_precio(CategoriaC), 2000);
-        assert_eq!(club.get_pagos(None).len(), 4);
+        assert_eq!(club.get_precio(CATEGORIA_A).unwrap(), 5000);
+        assert_eq!(club.get_precio(CATEGORIA_B).unwrap(), 3000);
+        assert_eq!(club.get_precio(CATEGORIA_C).unwrap(), 2000);
+        // Pero la información de los socios y sus pagos sólo la puede leer un auditor
+        assert_eq!(club.get_pagos(None), Err(ClubError::NoAutorizado));
+        assert_eq!(club.get_pagos(Some(0)), Err(ClubError::NoAutorizado));
+        assert_eq!(club.get_socios(), Err(ClubError::NoAutorizado));
+        assert_eq!(club.get_socio(0), Err(ClubError::NoAutorizado));
+        ser_carlos();
+        assert_eq!(club.get_pagos(None).unwrap().len(), 4);
+        assert_eq!(club.get_pagos(Some(0)).unwrap().len(), 4);
+        assert_eq!(club.get_socios().unwrap().len(), 1);
+        assert_eq!(club.get_socio(0).unwrap().get_nombre(), "Alicia");
+        // y no puede hacer nada más
+        assert_eq!(club.realizar_pago(0, 2000), Err(ClubError::NoAutorizado));
     }
 
     #[ink::test]
     fn autorizacion_hacer_cosas() {
         let mut club = generar_club();
         ser_alicia();
-        club.set_politica_autorizacion(true);
-        club.registrar_nuevo_socio(0, "Alicia".to_string(), CategoriaC);
+        club.set_politica_autorizacion(true).unwrap();
+        club.registrar_nuevo_socio(0, "Alicia".to_string(), CATEGORIA_C, vec![]).unwrap();
         ser_bob();
         // bob no debería poder hacer nada
-        assert!(panic::catch_unwind(|| {
-            club.clone().cambiar_dueño(bob());
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().agregar_autorizado(bob());
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_cantidad_pagos_bonificacion(1);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_porcentaje_bonificacion_pagos_consecutivos(100);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_politica_autorizacion(false);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_precio(CategoriaA, 0);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_precio(CategoriaB(Futbol), 0);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().set_precio(CategoriaC, 0);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().registrar_nuevo_socio(1, "Bob".to_string(), CategoriaA);
-        }).is_err());
-        assert!(panic::catch_unwind(|| {
-            club.clone().realizar_pago(0, u128::MAX);
-        }).is_err());
+        assert_eq!(club.proponer_dueño(bob()), Err(ClubError::NoEsDueño));
+        assert_eq!(club.otorgar_rol(bob(), Rol::Administrador), Err(ClubError::NoEsDueño));
+        assert_eq!(club.set_cantidad_pagos_bonificacion(1), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_porcentaje_bonificacion_pagos_consecutivos(100), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_politica_autorizacion(false), Err(ClubError::NoEsDueño));
+        assert_eq!(club.set_precio(CATEGORIA_A, 0), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_precio(CATEGORIA_B, 0), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_precio(CATEGORIA_C, 0), Err(ClubError::NoAutorizado));
+        assert_eq!(club.registrar_nuevo_socio(1, "Bob".to_string(), CATEGORIA_A, vec![]), Err(ClubError::NoAutorizado));
+        assert_eq!(club.realizar_pago(0, u128::MAX), Err(ClubError::NoAutorizado));
+    }
+
+    #[ink::test]
+    fn cambio_de_dueño_test() {
+        let mut club = generar_club();
+        assert_eq!(club.aceptar_dueño(), Err(ClubError::SinPropuestaDeDueño));
+        assert_eq!(club.cancelar_propuesta(), Err(ClubError::SinPropuestaDeDueño));
+        // Con la política abierta, sólo el dueño puede proponer otro dueño
+        ser_bob();
+        assert_eq!(club.proponer_dueño(bob()), Err(ClubError::NoEsDueño));
+        ser_alicia();
+        club.proponer_dueño(bob()).unwrap();
+        assert_eq!(club.get_dueño_propuesto(), Some(bob()));
+        // Hasta que no acepta, sigue siendo el mismo dueño
+        assert_eq!(club.get_dueño(), alicia());
+        assert_eq!(club.aceptar_dueño(), Err(ClubError::NoEsDueñoPropuesto));
+        ser_carlos();
+        assert_eq!(club.aceptar_dueño(), Err(ClubError::NoEsDueñoPropuesto));
+        assert_eq!(club.cancelar_propuesta(), Err(ClubError::NoEsDueño));
+        // El dueño puede cancelar la propuesta
+        ser_alicia();
+        club.cancelar_propuesta().unwrap();
+        assert_eq!(club.get_dueño_propuesto(), None);
+        ser_bob();
+        assert_eq!(club.aceptar_dueño(), Err(ClubError::SinPropuestaDeDueño));
+        // El propuesto la puede rechazar
+        ser_alicia();
+        club.proponer_dueño(bob()).unwrap();
+        ser_bob();
+        club.cancelar_propuesta().unwrap();
+        // Una nueva propuesta reemplaza a la anterior
+        ser_alicia();
+        club.proponer_dueño(bob()).unwrap();
+        club.proponer_dueño(carlos()).unwrap();
+        ser_bob();
+        assert_eq!(club.aceptar_dueño(), Err(ClubError::NoEsDueñoPropuesto));
+        ser_carlos();
+        club.aceptar_dueño().unwrap();
+        assert_eq!(club.get_dueño(), carlos());
+        assert_eq!(club.get_dueño_propuesto(), None);
+        assert!(club.soy_el_dueño());
+    }
+
+    #[ink::test]
+    fn roles_test() {
+        let mut club = generar_club();
+        club.set_politica_autorizacion(true).unwrap();
+        club.otorgar_rol(bob(), Rol::Tesorero).unwrap();
+        club.otorgar_rol(carlos(), Rol::Secretaria).unwrap();
+        club.otorgar_rol(dilan(), Rol::Administrador).unwrap();
+        // Cada rol sólo puede hacer lo suyo
+        ser_bob();
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_precio(CATEGORIA_C, 100), Err(ClubError::NoAutorizado));
+        ser_carlos();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.realizar_pago(0, 2000), Err(ClubError::NoAutorizado));
+        assert_eq!(club.cambiar_nombre("Otro".into()), Err(ClubError::NoAutorizado));
+        ser_dilan();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.set_cantidad_pagos_bonificacion(2).unwrap();
+        club.cambiar_nombre("Otro".into()).unwrap();
+        assert_eq!(club.realizar_pago(0, 2000), Err(ClubError::NoAutorizado));
+        assert_eq!(club.get_pagos(None), Err(ClubError::NoAutorizado));
+        ser_bob();
+        club.realizar_pago(0, 2000).unwrap();
+        assert_eq!(club.set_cantidad_pagos_bonificacion(3), Err(ClubError::NoAutorizado));
     }
 
     #[ink::test]
     fn autorizacion_con_cosas_test() {
         let mut club = generar_club();
-        club.set_politica_autorizacion(true);
+        club.set_politica_autorizacion(true).unwrap();
+    }
+
+    type Evento = <Club as ink::reflect::ContractEventBase>::Type;
+    fn eventos_emitidos() -> Vec<Evento> {
+        ink_env::test::recorded_events()
+            .map(|e| <Evento as scale::Decode>::decode(&mut &e.data[..]).unwrap())
+            .collect()
+    }
+
+    #[ink::test]
+    fn eventos_test() {
+        let mut club = generar_club();
+        club.set_cantidad_pagos_bonificacion(1).unwrap();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        // un pago fallido no emite nada
+        assert!(club.realizar_pago(0, 100).is_err());
+        club.otorgar_rol(bob(), Rol::Tesorero).unwrap();
+        club.revocar_rol(bob(), Rol::Tesorero).unwrap();
+        club.proponer_dueño(bob()).unwrap();
+        ser_bob();
+        club.aceptar_dueño().unwrap();
+        let eventos = eventos_emitidos();
+        assert_eq!(eventos.len(), 10);
+        assert!(matches!(eventos[0], Evento::PoliticaAutorizacionCambiada(_)));
+        assert!(matches!(eventos[1], Evento::BonificacionCambiada(_)));
+        assert!(matches!(eventos[2], Evento::PrecioCambiado(_)));
+        assert!(matches!(eventos[3], Evento::SocioRegistrado(_)));
+        assert!(matches!(eventos[4], Evento::PagoRealizado(_)));
+        assert!(matches!(eventos[5], Evento::BonificacionOtorgada(_)));
+        assert!(matches!(eventos[6], Evento::RolOtorgado(_)));
+        assert!(matches!(eventos[7], Evento::RolRevocado(_)));
+        assert!(matches!(eventos[8], Evento::DueñoPropuesto(_)));
+        assert!(matches!(eventos[9], Evento::DueñoCambiado(_)));
+        // el dni y la cuenta se pueden usar como filtro
+        let pago = ink_env::test::recorded_events().nth(4).unwrap();
+        assert_eq!(pago.topics.len(), 3);
+    }
+
+    fn set_tokens_transferidos(monto: u128) {
+        ink_env::test::set_value_transferred::<DefaultEnvironment>(monto);
+    }
+    fn balance(cuenta: TipoCuenta) -> u128 {
+        ink_env::test::get_account_balance::<DefaultEnvironment>(cuenta).unwrap()
+    }
+
+    #[ink::test]
+    fn pago_con_tokens_test() {
+        let mut club = generar_club();
+        club.set_politica_autorizacion(true).unwrap();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        // Cualquiera puede pagar con tokens, pero tiene que ser el monto exacto
+        ser_bob();
+        set_tokens_transferidos(99);
+        assert_eq!(club.realizar_pago_con_tokens(0), Err(ClubError::MontoIncorrecto));
+        set_tokens_transferidos(100);
+        assert_eq!(club.realizar_pago_con_tokens(1), Err(ClubError::SocioInexistente));
+        assert_eq!(club.realizar_pago_con_tokens(0), Ok(()));
+        assert_eq!(club.get_fondos(), 100);
+        ser_alicia();
+        assert!(club.get_pagos(Some(0)).unwrap()[0].es_pagado());
+        // Los pagos registrados sin tokens no suman fondos
+        club.realizar_pago(0, 100).unwrap();
+        assert_eq!(club.get_fondos(), 100);
+    }
+
+    #[ink::test]
+    fn retirar_fondos_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        set_tokens_transferidos(5000);
+        club.realizar_pago_con_tokens(0).unwrap();
+        // En los tests los tokens transferidos no llegan solos al contrato
+        let contrato = ink_env::test::callee::<DefaultEnvironment>();
+        ink_env::test::set_account_balance::<DefaultEnvironment>(contrato, 5000);
+        let (balance_bob, balance_carlos) = (balance(bob()), balance(carlos()));
+
+        // Sólo el dueño puede retirar, incluso con la política abierta
+        ser_bob();
+        assert_eq!(club.retirar_fondos(vec![(bob(), 1)]), Err(ClubError::NoEsDueño));
+        ser_alicia();
+        assert_eq!(club.retirar_fondos(vec![(bob(), 3000), (carlos(), 2001)]), Err(ClubError::FondosInsuficientes));
+        assert_eq!(club.retirar_fondos(vec![(bob(), 3000), (carlos(), 1500)]), Ok(()));
+        assert_eq!(club.get_fondos(), 500);
+        assert_eq!(balance(bob()), balance_bob + 3000);
+        assert_eq!(balance(carlos()), balance_carlos + 1500);
+    }
+
+    #[ink::test]
+    fn revertir_pago_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.set_cantidad_pagos_bonificacion(2).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.revertir_pago(0), Err(ClubError::PagoNoRevertible));
+        assert_eq!(club.revertir_pago(2), Err(ClubError::SocioInexistente));
+
+        // Bob paga con tokens la cuota de otro socio por error, y se le devuelven
+        ser_bob();
+        set_tokens_transferidos(100);
+        club.realizar_pago_con_tokens(1).unwrap();
+        let contrato = ink_env::test::callee::<DefaultEnvironment>();
+        ink_env::test::set_account_balance::<DefaultEnvironment>(contrato, 100);
+        let balance_bob = balance(bob());
+        ser_alicia();
+        let vencimiento = club.get_pagos(Some(1)).unwrap()[0].get_vencimiento();
+        club.revertir_pago(1).unwrap();
+        assert_eq!(balance(bob()), balance_bob + 100);
+        assert_eq!(club.get_fondos(), 0);
+        // Queda todo registrado
+        let pagos = club.get_pagos(Some(1)).unwrap();
+        assert_eq!(pagos.len(), 3);
+        assert!(pagos[0].es_pagado());
+        assert_eq!(pagos[0].get_revertido(), Some((club.obtener_fecha_actual(), alicia())));
+        assert!(pagos[1].es_cancelado());
+        assert!(!pagos[2].es_pagado());
+        assert_eq!(pagos[2].get_vencimiento(), vencimiento);
+        assert_eq!(club.get_monto_a_pagar(1), Ok(100));
+        assert_eq!(club.revertir_pago(1), Err(ClubError::PagoNoRevertible));
+
+        // La bonificación conseguida con el pago revertido se pierde, hasta que se vuelva a pagar
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(90));
+        club.revertir_pago(0).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(100));
+        club.realizar_pago(0, 100).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(90));
+
+        // Se pueden revertir varios pagos seguidos
+        club.revertir_pago(0).unwrap();
+        club.revertir_pago(0).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(100));
+        assert_eq!(club.revertir_pago(0), Err(ClubError::PagoNoRevertible));
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago_parcial(0, 10).unwrap();
+        assert_eq!(club.revertir_pago(0), Err(ClubError::PagoNoRevertible));
+
+        // Si ya se retiraron los fondos no se pueden devolver
+        set_tokens_transferidos(100);
+        club.realizar_pago_con_tokens(1).unwrap();
+        ink_env::test::set_account_balance::<DefaultEnvironment>(contrato, 100);
+        club.retirar_fondos(vec![(carlos(), 100)]).unwrap();
+        assert_eq!(club.revertir_pago(1), Err(ClubError::FondosInsuficientes));
+
+        // El cupón usado en el pendiente cancelado se puede volver a usar
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(2, 100).unwrap();
+        let hoy = club.obtener_fecha_actual();
+        club.crear_cupon("UNICO".into(), Descuento::Fijo(30), hoy, hoy, 1, vec![CATEGORIA_C]).unwrap();
+        club.aplicar_cupon(2, "UNICO".into()).unwrap();
+        assert_eq!(club.get_monto_a_pagar(2), Ok(70));
+        club.revertir_pago(2).unwrap();
+        assert_eq!(club.get_cupon("UNICO".into()).unwrap().get_usos(), 0);
+        assert_eq!(club.get_monto_a_pagar(2), Ok(100));
+        club.aplicar_cupon(2, "UNICO".into()).unwrap();
+        assert_eq!(club.get_monto_a_pagar(2), Ok(70));
+    }
+
+    #[ink::test]
+    fn revertir_pago_de_otra_membresia_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+        club.realizar_pago(1, 100).unwrap();
+
+        // Los pagos de antes de la baja no se revierten después de readmitirlo
+        club.dar_de_baja_socio(0).unwrap();
+        club.readmitir_socio(0, CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.revertir_pago(0), Err(ClubError::PagoNoRevertible));
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert!(!pagos[2].es_cancelado());
+        assert!(pagos.iter().all(|pago| !pago.es_revertido()));
+        club.realizar_pago(0, 100).unwrap();
+        club.revertir_pago(0).unwrap();
+        assert_eq!(club.revertir_pago(0), Err(ClubError::PagoNoRevertible));
+
+        // Ni los de antes de entrar a un grupo familiar después de dejarlo
+        club.agregar_dependiente(0, 1).unwrap();
+        club.quitar_dependiente(1).unwrap();
+        assert_eq!(club.revertir_pago(1), Err(ClubError::PagoNoRevertible));
+        assert!(!club.get_pagos(Some(1)).unwrap().last().unwrap().es_cancelado());
+    }
+
+    #[ink::test]
+    fn saldo_a_favor_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.acreditar_saldo(0, 0), Err(ClubError::ValorInvalido));
+        assert_eq!(club.acreditar_saldo(1, 10), Err(ClubError::SocioInexistente));
+        club.acreditar_saldo(0, 150).unwrap();
+        assert_eq!(club.get_saldo_a_favor(0), Ok(150));
+        // El pendiente ya estaba generado, así que el saldo se usa en los siguientes
+        assert_eq!(club.get_monto_a_pagar(0), Ok(100));
+        club.realizar_pago(0, 100).unwrap();
+        let pago = &club.get_pagos(Some(0)).unwrap()[1];
+        assert_eq!(pago.get_monto(), 0);
+        assert_eq!(pago.get_saldo_aplicado(), 100);
+        assert_eq!(club.get_saldo_a_favor(0), Ok(50));
+        club.realizar_pago(0, 0).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(50));
+        assert_eq!(club.get_saldo_a_favor(0), Ok(0));
+        // Al adelantar pagos también se usa
+        club.acreditar_saldo(0, 30).unwrap();
+        assert_eq!(club.get_monto_pago_adelantado(0, 2), Ok(50 + 70));
+
+        // Con tokens suma fondos
+        set_tokens_transferidos(20);
+        club.acreditar_saldo_con_tokens(0).unwrap();
+        assert_eq!(club.get_fondos(), 20);
+        assert_eq!(club.get_saldo_a_favor(0), Ok(50));
+
+        // El saldo de un grupo familiar es del titular
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.agregar_dependiente(0, 1).unwrap();
+        club.acreditar_saldo(1, 10).unwrap();
+        assert_eq!(club.get_saldo_a_favor(0), Ok(60));
+        assert_eq!(club.get_saldo_a_favor(1), Ok(60));
+        assert_eq!(club.get_socio(1).unwrap().get_saldo_a_favor(), 0);
+
+        // Si el pago en el que se usó se cancela, vuelve
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.acreditar_saldo(2, 30).unwrap();
+        club.realizar_pago(2, 100).unwrap();
+        assert_eq!(club.get_saldo_a_favor(2), Ok(0));
+        club.dar_de_baja_socio(2).unwrap();
+        assert_eq!(club.get_saldo_a_favor(2), Ok(30));
+
+        // El saldo de un socio que entra al grupo pasa al titular, y se queda con él cuando sale
+        club.registrar_nuevo_socio(3, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.acreditar_saldo(3, 40).unwrap();
+        club.agregar_dependiente(0, 3).unwrap();
+        assert_eq!(club.get_saldo_a_favor(3), Ok(100));
+        assert_eq!(club.get_socio(3).unwrap().get_saldo_a_favor(), 0);
+        assert_eq!(club.get_socio(0).unwrap().get_saldo_a_favor(), 100);
+        club.quitar_dependiente(3).unwrap();
+        assert_eq!(club.get_saldo_a_favor(3), Ok(0));
+        assert_eq!(club.get_saldo_a_favor(0), Ok(100));
+    }
+
+    #[ink::test]
+    fn autogestion_socio_test() {
+        let mut club = generar_club();
+        club.set_politica_autorizacion(true).unwrap();
+        club.otorgar_rol(carlos(), Rol::Secretaria).unwrap();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(1, "Bob".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(2, "Dilan".into(), CATEGORIA_C, vec![]).unwrap();
+
+        // Bob todavía no tiene la cuenta vinculada
+        ser_bob();
+        assert_eq!(club.mi_socio(), Err(ClubError::CuentaNoVinculada));
+        assert_eq!(club.mi_pago_pendiente(), Err(ClubError::CuentaNoVinculada));
+        assert_eq!(club.reclamar_cuenta(3), Err(ClubError::SocioInexistente));
+        club.reclamar_cuenta(1).unwrap();
+        assert_eq!(club.get_reclamo(1), Some(bob()));
+        // El reclamo lo tiene que aprobar la secretaría
+        assert_eq!(club.aprobar_reclamo(1), Err(ClubError::NoAutorizado));
+        assert_eq!(club.mi_socio(), Err(ClubError::CuentaNoVinculada));
+        ser_carlos();
+        assert_eq!(club.aprobar_reclamo(2), Err(ClubError::SinReclamo));
+        club.aprobar_reclamo(1).unwrap();
+        assert_eq!(club.get_reclamo(1), None);
+        // La secretaría también puede vincular cuentas directamente, pero cada cuenta sólo puede ser de un socio
+        assert_eq!(club.vincular_cuenta(2, bob()), Err(ClubError::CuentaYaVinculada));
+        club.vincular_cuenta(2, dilan()).unwrap();
+
+        ser_bob();
+        assert_eq!(club.mi_socio().unwrap().get_dni(), 1);
+        assert_eq!(club.mi_socio().unwrap().get_cuenta(), Some(bob()));
+        assert_eq!(club.mi_pago_pendiente().unwrap().get_monto(), 100);
+        assert_eq!(club.reclamar_cuenta(2), Err(ClubError::CuentaYaVinculada));
+        // Bob no es tesorero, pero puede pagar su propia cuota con tokens
+        assert_eq!(club.realizar_pago(1, 100), Err(ClubError::NoAutorizado));
+        set_tokens_transferidos(100);
+        club.pagar_mi_cuota().unwrap();
+        assert!(!club.mi_pago_pendiente().unwrap().es_pagado());
+        assert_eq!(club.get_fondos(), 100);
+        ser_dilan();
+        assert_eq!(club.mi_socio().unwrap().get_dni(), 2);
+        ser_eva();
+        assert_eq!(club.pagar_mi_cuota(), Err(ClubError::CuentaNoVinculada));
+    }
+
+    #[ink::test]
+    fn estados_socio_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.set_precio(CATEGORIA_A, 300).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.get_socio(0).unwrap().get_estado(), EstadoSocio::Activo);
+        assert_eq!(club.reactivar_socio(0), Err(ClubError::EstadoInvalido));
+        assert_eq!(club.readmitir_socio(0, CATEGORIA_A, vec![]), Err(ClubError::EstadoInvalido));
+        assert_eq!(club.suspender_socio(1), Err(ClubError::SocioInexistente));
+
+        // Suspendido no puede pagar
+        club.suspender_socio(0).unwrap();
+        assert_eq!(club.get_socio(0).unwrap().get_estado(), EstadoSocio::Suspendido);
+        assert_eq!(club.suspender_socio(0), Err(ClubError::EstadoInvalido));
+        assert_eq!(club.realizar_pago(0, 100), Err(ClubError::SocioNoActivo));
+        club.reactivar_socio(0).unwrap();
+        club.realizar_pago(0, 100).unwrap();
+
+        // Al darse de baja se cancela el pago pendiente y no se puede pagar
+        club.dar_de_baja_socio(0).unwrap();
+        assert_eq!(club.dar_de_baja_socio(0), Err(ClubError::EstadoInvalido));
+        assert_eq!(club.realizar_pago(0, 100), Err(ClubError::SocioNoActivo));
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos.len(), 2);
+        assert!(pagos[0].es_pagado());
+        assert!(pagos[1].es_cancelado());
+        let mut despues = club.obtener_fecha_actual();
+        despues.sumar_dias(100);
+        assert!(!pagos[1].es_moroso(despues));
+        // El dni no se puede volver a registrar, pero sí readmitir
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]), Err(ClubError::SocioYaRegistrado));
+        assert_eq!(club.readmitir_socio(0, CATEGORIA_B, vec![GIMNASIO]), Err(ClubError::CategoriaInvalida));
+        club.readmitir_socio(0, CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(club.get_socio(0).unwrap().get_categoria(), CATEGORIA_A);
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos.len(), 3);
+        assert_eq!(pagos[2].get_monto(), 300);
+        club.realizar_pago(0, 300).unwrap();
+
+        // Sólo la secretaría puede cambiar el estado
+        club.set_politica_autorizacion(true).unwrap();
+        ser_bob();
+        assert_eq!(club.suspender_socio(0), Err(ClubError::NoAutorizado));
+        assert_eq!(club.dar_de_baja_socio(0), Err(ClubError::NoAutorizado));
+    }
+
+    #[ink::test]
+    fn cambiar_categoria_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_A, 600).unwrap();
+        club.set_precio(CATEGORIA_B, 300).unwrap();
+        club.set_precio(CATEGORIA_C, 150).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.cambiar_categoria(0, CATEGORIA_C, vec![]), Err(ClubError::ValorInvalido));
+        assert_eq!(club.cambiar_categoria(0, CATEGORIA_B, vec![GIMNASIO]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.cambiar_categoria(1, CATEGORIA_A, vec![]), Err(ClubError::SocioInexistente));
+        club.realizar_pago(0, 150).unwrap();
+
+        // Faltan más de 30 días para el vencimiento: se cobra todo con el precio nuevo
+        club.cambiar_categoria(0, CATEGORIA_B, vec![FUTBOL]).unwrap();
+        assert_eq!(club.get_socio(0).unwrap().get_categoria(), CATEGORIA_B);
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos[0].get_categoria(), CATEGORIA_C);
+        assert_eq!(pagos[0].get_categoria_anterior(), None);
+        assert_eq!(pagos[1].get_monto(), 300);
+        assert_eq!(pagos[1].get_categoria(), CATEGORIA_B);
+        assert_eq!(pagos[1].get_categoria_anterior(), Some(CATEGORIA_C));
+
+        // Faltan 10 días: 20 días al precio anterior y 10 al nuevo
+        avanzar_dias(30);
+        club.cambiar_categoria(0, CATEGORIA_A, vec![]).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos[1].get_monto(), 200 + 200);
+        assert_eq!(pagos[1].get_categoria(), CATEGORIA_A);
+        assert_eq!(pagos[1].get_categoria_anterior(), Some(CATEGORIA_B));
+        club.realizar_pago(0, 400).unwrap();
+        // El siguiente pago ya se genera con la categoría nueva
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos[2].get_monto(), 600);
+        assert_eq!(pagos[2].get_categoria(), CATEGORIA_A);
+        assert_eq!(pagos[2].get_categoria_anterior(), None);
+
+        // Con el pago vencido no cambia el monto ni la categoría
+        avanzar_dias(41);
+        club.cambiar_categoria(0, CATEGORIA_C, vec![]).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos[2].get_monto(), 600);
+        assert_eq!(pagos[2].get_categoria(), CATEGORIA_A);
+        assert_eq!(pagos[2].get_categoria_anterior(), None);
+
+        club.suspender_socio(0).unwrap();
+        assert_eq!(club.cambiar_categoria(0, CATEGORIA_A, vec![]), Err(ClubError::SocioNoActivo));
+    }
+
+    #[ink::test]
+    fn cambiar_categoria_con_saldo_a_favor_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_A, 600).unwrap();
+        club.set_precio(CATEGORIA_C, 150).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.acreditar_saldo(0, 150).unwrap();
+        club.acreditar_saldo(1, 1000).unwrap();
+        club.realizar_pago(0, 150).unwrap();
+        club.realizar_pago(1, 600).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(0));
+        assert_eq!(club.get_saldo_a_favor(1), Ok(400));
+
+        // Faltan 10 días: se prorratea el monto sin el saldo, y el saldo se vuelve a descontar
+        avanzar_dias(30);
+        club.cambiar_categoria(0, CATEGORIA_A, vec![]).unwrap();
+        let pago = &club.get_pagos(Some(0)).unwrap()[1];
+        assert_eq!(pago.get_monto(), 100 + 200 - 150);
+        assert_eq!(pago.get_saldo_aplicado(), 150);
+        assert_eq!(club.get_saldo_a_favor(0), Ok(0));
+
+        // Si la nueva categoría es más barata, el saldo que sobra vuelve al socio
+        club.cambiar_categoria(1, CATEGORIA_C, vec![]).unwrap();
+        let pago = &club.get_pagos(Some(1)).unwrap()[1];
+        assert_eq!(pago.get_monto(), 0);
+        assert_eq!(pago.get_saldo_aplicado(), 400 + 50);
+        assert_eq!(club.get_saldo_a_favor(1), Ok(400 + 150));
+    }
+
+    #[ink::test]
+    fn cambiar_categoria_con_pagos_facturados_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_A, 600).unwrap();
+        club.set_precio(CATEGORIA_C, 300).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        // El pendiente vence el 11/1 y ya se facturó el que vence el 10/2
+        avanzar_dias(35);
+        club.facturar(10).unwrap();
+
+        // Faltan 5 de los 30 días del período del facturado: se recalcula ese, y el pendiente vencido no cambia
+        club.cambiar_categoria(0, CATEGORIA_A, vec![]).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos.len(), 2);
+        assert_eq!(pagos[0].get_monto(), 300);
+        assert_eq!(pagos[0].get_categoria(), CATEGORIA_C);
+        assert_eq!(pagos[0].get_categoria_anterior(), None);
+        assert_eq!(pagos[1].get_monto(), 250 + 100);
+        assert_eq!(pagos[1].get_categoria(), CATEGORIA_A);
+        assert_eq!(pagos[1].get_categoria_anterior(), Some(CATEGORIA_C));
+    }
+
+    #[ink::test]
+    fn pago_adelantado_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.set_cantidad_pagos_bonificacion(3).unwrap();
+        club.set_porcentaje_bonificacion_pagos_consecutivos(50).unwrap();
+        assert_eq!(club.set_porcentaje_descuento_pago_adelantado(100), Err(ClubError::ValorInvalido));
+        club.set_porcentaje_descuento_pago_adelantado(20).unwrap();
+        assert_eq!(club.get_porcentaje_descuento_pago_adelantado(), 20);
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.get_monto_pago_adelantado(0, 0), Err(ClubError::ValorInvalido));
+        assert_eq!(club.get_monto_pago_adelantado(1, 2), Err(ClubError::SocioInexistente));
+        // Un solo periodo es un pago normal
+        assert_eq!(club.get_monto_pago_adelantado(0, 1), Ok(100));
+
+        // El pendiente se paga completo, los siguientes con descuento y el cuarto tiene la bonificación, que es mayor
+        let total = 100 + 80 + 80 + 50 + 80;
+        assert_eq!(club.get_monto_pago_adelantado(0, 5), Ok(total));
+        assert_eq!(club.realizar_pago_adelantado(0, 5, total - 1), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.get_pagos(Some(0)).unwrap().len(), 1);
+        club.realizar_pago_adelantado(0, 5, total).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert_eq!(pagos.len(), 6);
+        assert!(pagos[..5].iter().all(|p| p.es_pagado() && p.es_pagado_a_tiempo().unwrap()));
+        assert_eq!(pagos.iter().map(|p| p.get_monto()).collect::<Vec<_>>(), vec![100, 80, 80, 50, 80, 100]);
+        assert_eq!(pagos.iter().map(|p| p.get_es_adelantado()).collect::<Vec<_>>(), vec![false, true, true, true, true, false]);
+        assert!(pagos[3].get_es_descuento());
+        assert!(pagos[1].get_vencimiento().es_mayor(&pagos[0].get_vencimiento()));
+        // El nuevo pago pendiente es el sexto, y la racha sigue contando los adelantados
+        assert!(!pagos[5].es_pagado());
+        club.realizar_pago(0, 100).unwrap();
+
+        // Con tokens. Con este pago se completa la racha, así que el siguiente tiene la bonificación
+        let monto = club.get_monto_pago_adelantado(0, 2).unwrap();
+        assert_eq!(monto, 100 + 50);
+        set_tokens_transferidos(monto);
+        club.realizar_pago_adelantado_con_tokens(0, 2).unwrap();
+        assert_eq!(club.get_fondos(), monto);
+
+        club.set_politica_autorizacion(true).unwrap();
+        ser_bob();
+        assert_eq!(club.realizar_pago_adelantado(0, 1, 100), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_porcentaje_descuento_pago_adelantado(10), Err(ClubError::NoAutorizado));
+    }
+
+    #[ink::test]
+    fn pagos_parciales_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_C, 100).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.realizar_pago_parcial(0, 0), Err(ClubError::ValorInvalido));
+        assert_eq!(club.realizar_pago_parcial(0, 101), Err(ClubError::MontoIncorrecto));
+        club.realizar_pago_parcial(0, 30).unwrap();
+        avanzar_dias(5);
+        set_tokens_transferidos(50);
+        club.realizar_pago_parcial_con_tokens(0).unwrap();
+        let pago = &club.get_pagos(Some(0)).unwrap()[0];
+        assert!(!pago.es_pagado());
+        assert_eq!(pago.get_monto_pagado(), 80);
+        assert_eq!(pago.get_monto_restante(), 20);
+        assert_eq!(pago.get_pagos_parciales().len(), 2);
+        // Todavía no está completo, así que pasado el vencimiento es moroso
+        let mut despues = club.obtener_fecha_actual();
+        despues.sumar_dias(6);
+        assert!(pago.es_moroso(despues));
+        // El pago normal tiene que completar lo que falta
+        assert_eq!(club.realizar_pago(0, 100), Err(ClubError::MontoIncorrecto));
+        assert_eq!(club.get_monto_pago_adelantado(0, 2), Ok(120));
+
+        // Se completa después del vencimiento, así que no es a tiempo aunque haya empezado antes
+        avanzar_dias(6);
+        club.realizar_pago_parcial(0, 20).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert!(pagos[0].es_pagado());
+        assert_eq!(pagos[0].get_pagado(), Some(club.obtener_fecha_actual()));
+        assert_eq!(pagos[0].es_pagado_a_tiempo(), Some(false));
+        assert_eq!(pagos[0].get_monto_pagado(), 100);
+        assert_eq!(pagos[0].get_pagos_parciales().iter().map(|p| p.1).collect::<Vec<_>>(), vec![30, 50, 20]);
+        // Se genera el siguiente pago
+        assert_eq!(pagos.len(), 2);
+        assert_eq!(pagos[1].get_monto_pagado(), 0);
+        assert!(pagos[1].get_pagos_parciales().is_empty());
+
+        // Con un pago parcial, el resto se puede pagar con el pago normal
+        club.realizar_pago_parcial(0, 60).unwrap();
+        club.realizar_pago(0, 40).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert!(pagos[1].es_pagado_a_tiempo().unwrap());
+        assert_eq!(pagos[1].get_pagos_parciales().iter().map(|p| p.1).collect::<Vec<_>>(), vec![60, 40]);
+        assert_eq!(club.get_fondos(), 50);
+    }
+
+    #[ink::test]
+    fn recargos_test() {
+        assert_eq!(Recargo::Porcentaje(10).calcular(200, 0), Some(0));
+        assert_eq!(Recargo::Fijo(50).calcular(200, 40), Some(50));
+        assert_eq!(Recargo::Porcentaje(10).calcular(200, 40), Some(20));
+        let interes = Recargo::InteresDiario { por_mil_diario: 5, tope_porcentaje: 15 };
+        assert_eq!(interes.calcular(1000, 1), Some(5));
+        assert_eq!(interes.calcular(1000, 20), Some(100));
+        assert_eq!(interes.calcular(1000, 40), Some(150));
+        assert_eq!(Recargo::Porcentaje(10).calcular(u128::MAX, 1), None);
+
+        let mut club = generar_club();
+        assert_eq!(club.get_recargo(), Recargo::Ninguno);
+        assert_eq!(club.set_recargo(Recargo::Fijo(0)), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_recargo(Recargo::InteresDiario { por_mil_diario: 5, tope_porcentaje: 0 }), Err(ClubError::ValorInvalido));
+        club.set_recargo(interes).unwrap();
+        club.set_precio(CATEGORIA_C, 1000).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        // A tiempo no hay recargo
+        avanzar_dias(10);
+        assert_eq!(club.get_recargo_pendiente(0), Ok(0));
+        assert_eq!(club.get_monto_a_pagar(0), Ok(1000));
+        // 20 días de atraso
+        avanzar_dias(20);
+        assert_eq!(club.get_recargo_pendiente(0), Ok(100));
+        assert_eq!(club.get_monto_a_pagar(0), Ok(1100));
+        assert_eq!(club.realizar_pago(0, 1000), Err(ClubError::MontoIncorrecto));
+        // Los pagos parciales se descuentan de lo que hay que pagar
+        club.realizar_pago_parcial(0, 600).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(500));
+        assert_eq!(club.realizar_pago_parcial(0, 501), Err(ClubError::MontoIncorrecto));
+        // Si se deja pasar más tiempo, el recargo sigue aumentando hasta el tope
+        avanzar_dias(20);
+        assert_eq!(club.get_monto_a_pagar(0), Ok(550));
+        club.realizar_pago(0, 550).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert!(pagos[0].es_pagado());
+        assert_eq!(pagos[0].get_monto(), 1000);
+        assert_eq!(pagos[0].get_recargo(), 150);
+        assert_eq!(pagos[0].get_monto_pagado(), 1150);
+        assert_eq!(pagos[0].get_pagos_parciales().iter().map(|p| p.1).collect::<Vec<_>>(), vec![600, 550]);
+        // El siguiente pago no arrastra el recargo. Como vence antes de hoy, ya tiene recargo
+        assert_eq!(pagos[1].get_recargo(), 0);
+        assert_eq!(club.get_recargo_pendiente(0), Ok(50));
+    }
+
+    #[ink::test]
+    fn ciclo_facturacion_test() {
+        let mut club = generar_club();
+        assert_eq!(club.get_dias_primer_vencimiento(), 10);
+        assert_eq!(club.get_ciclo_facturacion(), CicloFacturacion::Dias(30));
+        assert_eq!(club.set_ciclo_facturacion(CicloFacturacion::Dias(0)), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_ciclo_facturacion(CicloFacturacion::Meses { meses: 0, dia: None }), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_ciclo_facturacion(CicloFacturacion::Meses { meses: 1, dia: Some(32) }), Err(ClubError::ValorInvalido));
+
+        // Cada 90 días, con 5 días para el primer pago
+        club.set_dias_primer_vencimiento(5).unwrap();
+        club.set_ciclo_facturacion(CicloFacturacion::Dias(90)).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(0, club.get_precio(CATEGORIA_C).unwrap()).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        let mut esperado = club.obtener_fecha_actual();
+        esperado.sumar_dias(5);
+        assert_eq!(pagos[0].get_vencimiento(), esperado);
+        esperado.sumar_dias(90);
+        assert_eq!(pagos[1].get_vencimiento(), esperado);
+
+        // El mismo día de cada mes
+        club.set_ciclo_facturacion(CicloFacturacion::Meses { meses: 1, dia: None }).unwrap();
+        // El primer pago vence el 31 de enero
+        ink_env::test::set_block_timestamp::<DefaultEnvironment>(0);
+        club.set_dias_primer_vencimiento(30).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        for _ in 0..3 {
+            club.realizar_pago(1, club.get_precio(CATEGORIA_C).unwrap()).unwrap();
+        }
+        let vencimientos: Vec<Fecha> = club.get_pagos(Some(1)).unwrap().iter().map(|p| p.get_vencimiento()).collect();
+        let esperados = [(31, 1), (28, 2), (31, 3), (30, 4)];
+        for (vencimiento, (dia, mes)) in vencimientos.iter().zip(esperados) {
+            assert_eq!(*vencimiento, Fecha::new(dia, mes, 1970).unwrap());
+        }
+
+        // Trimestral, el día 10
+        club.set_ciclo_facturacion(CicloFacturacion::Meses { meses: 3, dia: Some(10) }).unwrap();
+        club.realizar_pago(1, club.get_precio(CATEGORIA_C).unwrap()).unwrap();
+        assert_eq!(club.get_pagos(Some(1)).unwrap()[4].get_vencimiento(), Fecha::new(10, 7, 1970).unwrap());
+        assert_eq!(CicloFacturacion::Meses { meses: 3, dia: Some(10) }.dias_antes_de(Fecha::new(10, 7, 1970).unwrap()), 91);
+
+        club.set_politica_autorizacion(true).unwrap();
+        ser_bob();
+        assert_eq!(club.set_dias_primer_vencimiento(1), Err(ClubError::NoAutorizado));
+        assert_eq!(club.set_ciclo_facturacion(CicloFacturacion::Dias(1)), Err(ClubError::NoAutorizado));
+    }
+
+    #[ink::test]
+    fn categorias_y_actividades_test() {
+        let mut club = generar_club();
+        assert_eq!(club.get_actividades().len(), 8);
+        assert_eq!(club.get_categorias().len(), 3);
+        assert_eq!(club.get_categoria(3), Err(ClubError::CategoriaInexistente));
+        assert_eq!(club.get_precio(3), Err(ClubError::CategoriaInexistente));
+        assert_eq!(club.set_precio(3, 100), Err(ClubError::CategoriaInexistente));
+
+        // Nueva actividad, que se puede agregar a las categorías existentes
+        assert_eq!(club.registrar_actividad("Tenis".into()), Err(ClubError::NombreRepetido));
+        let voley = club.registrar_actividad("Vóley".into()).unwrap();
+        assert_eq!(voley, 8);
+        assert_eq!(club.get_actividades()[8], "Vóley");
+        let mut incluidas_a = club.get_categoria(CATEGORIA_A).unwrap().get_incluidas().to_vec();
+        incluidas_a.push(voley);
+        club.modificar_actividades_categoria(CATEGORIA_A, incluidas_a, vec![], 0).unwrap();
+
+        // Nueva categoría: gimnasio y dos deportes a elección entre fútbol, tenis y vóley
+        assert_eq!(club.registrar_categoria("C".into(), 100, vec![], vec![], 0), Err(ClubError::NombreRepetido));
+        assert_eq!(club.registrar_categoria("Juvenil".into(), 100, vec![GIMNASIO], vec![FUTBOL, 9], 1), Err(ClubError::ActividadInexistente));
+        assert_eq!(club.registrar_categoria("Juvenil".into(), 100, vec![GIMNASIO], vec![FUTBOL, voley], 3), Err(ClubError::ValorInvalido));
+        let juvenil = club.registrar_categoria("Juvenil".into(), 1500, vec![GIMNASIO], vec![FUTBOL, TENIS, voley], 2).unwrap();
+        assert_eq!(juvenil, 3);
+        assert_eq!(club.get_precio(juvenil), Ok(1500));
+        assert_eq!(club.get_categoria(juvenil).unwrap().get_nombre(), "Juvenil");
+
+        // Hay que elegir exactamente dos actividades elegibles distintas
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), juvenil, vec![FUTBOL]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), juvenil, vec![FUTBOL, FUTBOL]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), juvenil, vec![FUTBOL, GIMNASIO]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), 4, vec![]), Err(ClubError::CategoriaInexistente));
+        club.registrar_nuevo_socio(0, "".into(), juvenil, vec![FUTBOL, voley]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_A, vec![]).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[0].get_monto(), 1500);
+        assert_eq!(club.puede_acceder_a(0, GIMNASIO), Ok(true));
+        assert_eq!(club.puede_acceder_a(0, voley), Ok(true));
+        assert_eq!(club.puede_acceder_a(0, TENIS), Ok(false));
+        assert_eq!(club.puede_acceder_a(1, voley), Ok(true));
+        assert_eq!(club.puede_acceder_a(2, voley), Err(ClubError::SocioInexistente));
+
+        // Si el vóley deja de ser elegible, los que lo eligieron ya no pueden acceder
+        club.modificar_actividades_categoria(juvenil, vec![GIMNASIO], vec![FUTBOL, TENIS], 1).unwrap();
+        assert_eq!(club.puede_acceder_a(0, voley), Ok(false));
+        assert_eq!(club.puede_acceder_a(0, FUTBOL), Ok(true));
+        assert_eq!(club.modificar_actividades_categoria(5, vec![], vec![], 0), Err(ClubError::CategoriaInexistente));
+
+        club.set_politica_autorizacion(true).unwrap();
+        ser_bob();
+        assert_eq!(club.registrar_actividad("Handball".into()), Err(ClubError::NoAutorizado));
+        assert_eq!(club.registrar_categoria("D".into(), 1, vec![], vec![], 0), Err(ClubError::NoAutorizado));
+        assert_eq!(club.modificar_actividades_categoria(juvenil, vec![], vec![], 0), Err(ClubError::NoAutorizado));
+    }
+
+    #[ink::test]
+    fn actividades_extra_test() {
+        let mut club = generar_club();
+        club.set_precio(CATEGORIA_B, 1000).unwrap();
+        // Al principio sólo se puede elegir un deporte
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![TENIS, PADDLE]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.set_actividades_extra(CATEGORIA_B, 0, 300), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_actividades_extra(3, 2, 300), Err(ClubError::CategoriaInexistente));
+        club.set_actividades_extra(CATEGORIA_B, 3, 300).unwrap();
+        let categoria_b = club.get_categoria(CATEGORIA_B).unwrap();
+        assert_eq!(categoria_b.get_maximo_a_elegir(), 3);
+        assert_eq!(categoria_b.get_precio_por_actividad_extra(), 300);
+
+        // Ahora se pueden elegir hasta 3, pagando cada uno después del primero
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![TENIS, PADDLE, FUTBOL, 2]), Err(ClubError::CategoriaInvalida));
+        assert_eq!(club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![TENIS, TENIS]), Err(ClubError::CategoriaInvalida));
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_B, vec![TENIS, PADDLE]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[0].get_monto(), 1300);
+        assert_eq!(club.get_pagos(Some(1)).unwrap()[0].get_monto(), 1000);
+        assert_eq!(club.puede_acceder_a(0, TENIS), Ok(true));
+        assert_eq!(club.puede_acceder_a(0, PADDLE), Ok(true));
+        assert_eq!(club.puede_acceder_a(0, FUTBOL), Ok(false));
+        assert_eq!(club.puede_acceder_a(1, PADDLE), Ok(false));
+
+        // Los pagos siguientes mantienen el precio de los extra
+        club.realizar_pago(0, 1300).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[1].get_monto(), 1300);
+        // Agregar un deporte también es un cambio de categoría
+        club.cambiar_categoria(1, CATEGORIA_B, vec![TENIS, FUTBOL, PADDLE]).unwrap();
+        assert_eq!(club.puede_acceder_a(1, PADDLE), Ok(true));
+        assert_eq!(club.get_socio(1).unwrap().get_actividades_elegidas(), &[TENIS, FUTBOL, PADDLE]);
+    }
+
+    #[ink::test]
+    fn precios_programados_test() {
+        let mut club = generar_club();
+        let hoy = club.obtener_fecha_actual();
+        let mut ayer = hoy;
+        ayer.restar_dias(1);
+        assert_eq!(club.programar_precio(CATEGORIA_C, ayer, 2500), Err(ClubError::ValorInvalido));
+        assert_eq!(club.programar_precio(3, hoy, 2500), Err(ClubError::CategoriaInexistente));
+        assert_eq!(club.get_historial_precios(3), Err(ClubError::CategoriaInexistente));
+
+        // El precio programado no cambia el actual, pero sí el de los pagos que vencen desde esa fecha
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        let primer_pago = club.get_pagos(Some(0)).unwrap()[0].clone();
+        let mut desde = primer_pago.get_vencimiento();
+        desde.sumar_dias(1);
+        club.programar_precio(CATEGORIA_C, desde, 2500).unwrap();
+        assert_eq!(club.get_precio(CATEGORIA_C), Ok(2000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, primer_pago.get_vencimiento()), Ok(2000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, desde), Ok(2500));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, ayer), Ok(2000));
+        club.realizar_pago(0, 2000).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[1].get_monto(), 2500);
+
+        // Reprogramar la misma fecha reemplaza el precio, y cambiar el precio desde hoy agrega otro al historial
+        club.programar_precio(CATEGORIA_C, desde, 2400).unwrap();
+        avanzar_dias(1);
+        let mañana = club.obtener_fecha_actual();
+        club.set_precio(CATEGORIA_C, 2200).unwrap();
+        assert_eq!(club.get_precio(CATEGORIA_C), Ok(2200));
+        assert_eq!(club.get_historial_precios(CATEGORIA_C), Ok(vec![(hoy, 2000), (mañana, 2200), (desde, 2400)]));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, desde), Ok(2400));
+    }
+
+    #[ink::test]
+    fn indexacion_test() {
+        let mut club = generar_club();
+        let febrero = Fecha::new(1, 2, 1970).unwrap();
+        assert_eq!(club.set_indexacion(0, 1, febrero, 100, Redondeo::Cercano), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_indexacion(100, 0, febrero, 100, Redondeo::Cercano), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_indexacion(100, 1, febrero, 0, Redondeo::Cercano), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_indexacion(100, 1, Fecha::new(31, 12, 1969).unwrap(), 100, Redondeo::Cercano), Err(ClubError::ValorInvalido));
+
+        // 10% por mes calendario, redondeado a 100
+        club.set_indexacion(100, 1, febrero, 100, Redondeo::Cercano).unwrap();
+        assert_eq!(club.get_indexacion().unwrap().get_cada_meses(), 1);
+        assert_eq!(club.get_precio(CATEGORIA_C), Ok(2000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(31, 1, 1970).unwrap()), Ok(2000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, febrero), Ok(2200));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(1, 3, 1970).unwrap()), Ok(2400));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(1, 4, 1970).unwrap()), Ok(2600));
+        assert_eq!(club.get_precio_en(CATEGORIA_A, febrero), Ok(5500));
+
+        // Los pagos se generan con el precio ajustado a su vencimiento
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago(0, 2000).unwrap();
+        let pago = club.get_pagos(Some(0)).unwrap()[1].clone();
+        assert_eq!(pago.get_vencimiento(), Fecha::new(10, 2, 1970).unwrap());
+        assert_eq!(pago.get_monto(), 2200);
+
+        // Un precio fijado a mano reemplaza al ajustado, y los ajustes siguientes se aplican sobre él
+        let precio_fijado = Fecha::new(15, 2, 1970).unwrap();
+        club.programar_precio(CATEGORIA_C, precio_fijado, 3000).unwrap();
+        assert_eq!(club.get_precio_en(CATEGORIA_C, febrero), Ok(2200));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, precio_fijado), Ok(3000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(1, 3, 1970).unwrap()), Ok(3300));
+        assert_eq!(club.get_historial_precios(CATEGORIA_C).unwrap().len(), 2);
+
+        // El historial incluye los ajustes que ya rigieron, y quedan al quitar la indexación
+        avanzar_dias(60);
+        let historial = vec![
+            (Fecha::new(1, 1, 1970).unwrap(), 2000),
+            (febrero, 2200),
+            (precio_fijado, 3000),
+            (Fecha::new(1, 3, 1970).unwrap(), 3300),
+        ];
+        assert_eq!(club.get_historial_precios(CATEGORIA_C), Ok(historial.clone()));
+        club.quitar_indexacion().unwrap();
+        assert_eq!(club.get_indexacion(), None);
+        assert_eq!(club.get_historial_precios(CATEGORIA_C), Ok(historial));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(1, 4, 1970).unwrap()), Ok(3300));
+
+        // 5% cada 3 meses desde hoy, redondeado para arriba a 1000
+        let hoy = club.obtener_fecha_actual();
+        club.set_indexacion(50, 3, hoy, 1000, Redondeo::Arriba).unwrap();
+        assert_eq!(club.get_precio(CATEGORIA_C), Ok(4000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(1, 6, 1970).unwrap()), Ok(4000));
+        assert_eq!(club.get_precio_en(CATEGORIA_C, Fecha::new(2, 6, 1970).unwrap()), Ok(5000));
+    }
+
+    #[ink::test]
+    fn facturacion_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+        club.registrar_nuevo_socio(3, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(4, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.agregar_dependiente(0, 3).unwrap();
+        club.suspender_socio(2).unwrap();
+        assert_eq!(club.facturar(0), Err(ClubError::ValorInvalido));
+        // Todavía no empezó ningún período nuevo
+        assert_eq!(club.facturar(10), Ok(true));
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 1);
+
+        // Pasan tres vencimientos sin que nadie pague: se facturan de a partes
+        avanzar_dias(75);
+        assert_eq!(club.facturar(1), Ok(false));
+        assert_eq!(club.get_siguiente_socio_a_facturar(), 1);
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 1);
+        assert_eq!(club.facturar(10), Ok(true));
+        assert_eq!(club.get_siguiente_socio_a_facturar(), 0);
+        let pagos = club.get_pagos(Some(1)).unwrap();
+        assert_eq!(pagos.len(), 4);
+        assert!(pagos.iter().all(|pago| !pago.es_pagado() && pago.get_monto() == 2000));
+        assert_eq!(pagos[3].get_vencimiento(), Fecha::new(11, 4, 1970).unwrap());
+        // Los dependientes se facturan con el titular, y los suspendidos no se facturan
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[3].get_monto(), 4000);
+        assert_eq!(club.get_pagos(Some(3)).unwrap().len(), 1);
+        assert_eq!(club.get_pagos(Some(2)).unwrap().len(), 1);
+        // Volver a facturar no genera nada
+        assert_eq!(club.facturar(10), Ok(true));
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 4);
+
+        // Se paga primero el más antiguo, y los facturados se pagan antes de generar pagos nuevos
+        assert_eq!(club.get_monto_a_pagar(1), Ok(2000));
+        club.realizar_pago(1, 2000).unwrap();
+        let pagos = club.get_pagos(Some(1)).unwrap();
+        assert_eq!(pagos.len(), 4);
+        assert!(pagos[0].es_pagado() && !pagos[1].es_pagado());
+        assert_eq!(club.revertir_pago(1), Err(ClubError::PagoNoRevertible));
+        assert_eq!(club.get_monto_pago_adelantado(1, 3), Ok(6000));
+        club.realizar_pago_adelantado(1, 3, 6000).unwrap();
+        let pagos = club.get_pagos(Some(1)).unwrap();
+        assert_eq!(pagos.len(), 5);
+        assert!(pagos[3].es_pagado() && !pagos[4].es_pagado());
+        assert_eq!(pagos[4].get_vencimiento(), Fecha::new(11, 5, 1970).unwrap());
+        assert_eq!(club.facturar(10), Ok(true));
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 5);
+
+        // Al dar de baja se cancelan también los facturados
+        club.dar_de_baja_socio(4).unwrap();
+        assert!(club.get_pagos(Some(4)).unwrap().iter().all(|pago| pago.es_cancelado()));
+    }
+
+    #[ink::test]
+    fn estado_de_cuenta_test() {
+        let mut club = generar_club();
+        club.set_recargo(Recargo::Porcentaje(10)).unwrap();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.estado_de_cuenta(5), Err(ClubError::SocioInexistente));
+        // Al principio sólo adeuda el primer pago, que todavía no venció
+        let estado = club.estado_de_cuenta(0).unwrap();
+        assert_eq!(estado.get_periodos().len(), 1);
+        assert_eq!(estado.get_periodos()[0].get_dias_de_atraso(), 0);
+        assert_eq!(estado.get_deuda_total(), 2000);
+
+        avanzar_dias(40);
+        club.facturar(10).unwrap();
+        club.realizar_pago_parcial(0, 1000).unwrap();
+        club.acreditar_saldo(0, 500).unwrap();
+        let estado = club.estado_de_cuenta(0).unwrap();
+        let periodos = estado.get_periodos();
+        assert_eq!(periodos.len(), 2);
+        assert_eq!(periodos[0].get_vencimiento(), Fecha::new(11, 1, 1970).unwrap());
+        assert_eq!(periodos[0].get_dias_de_atraso(), 30);
+        assert_eq!(periodos[0].get_recargo(), 200);
+        assert_eq!(periodos[0].get_pagado(), 1000);
+        assert_eq!(periodos[0].get_deuda(), 1200);
+        assert_eq!(periodos[1].get_vencimiento(), Fecha::new(10, 2, 1970).unwrap());
+        assert_eq!(periodos[1].get_dias_de_atraso(), 0);
+        assert_eq!(periodos[1].get_deuda(), 2000);
+        assert_eq!(estado.get_deuda_total(), 3200);
+        assert_eq!(estado.get_saldo_a_favor(), 500);
+
+        // Los dependientes tienen el estado de cuenta del grupo, y los dados de baja no adeudan nada
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.agregar_dependiente(0, 2).unwrap();
+        assert_eq!(club.estado_de_cuenta(2).unwrap().get_dni(), 0);
+        club.dar_de_baja_socio(1).unwrap();
+        let estado = club.estado_de_cuenta(1).unwrap();
+        assert!(estado.get_periodos().is_empty());
+        assert_eq!(estado.get_deuda_total(), 0);
+    }
+
+    #[ink::test]
+    fn paginacion_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_A, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(3, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+        club.registrar_nuevo_socio(4, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.suspender_socio(4).unwrap();
+        let dnis = |(socios, siguiente): (Vec<Socio>, Option<u64>)| (socios.iter().map(|socio| socio.get_dni()).collect::<Vec<_>>(), siguiente);
+
+        assert_eq!(club.get_socios_pagina(0, 0, FiltroSocios::default()), Err(ClubError::ValorInvalido));
+        assert_eq!(club.get_socios_pagina(0, 2, FiltroSocios::default()).map(dnis), Ok((vec![0, 1], Some(2))));
+        assert_eq!(club.get_socios_pagina(4, 2, FiltroSocios::default()).map(dnis), Ok((vec![4], None)));
+        assert_eq!(club.get_socios_pagina(10, 2, FiltroSocios::default()).map(dnis), Ok((vec![], None)));
+        // Los filtros se aplican a los socios recorridos en cada página
+        let filtro = FiltroSocios { categoria: Some(CATEGORIA_C), ..Default::default() };
+        assert_eq!(club.get_socios_pagina(0, 2, filtro.clone()).map(dnis), Ok((vec![1], Some(2))));
+        assert_eq!(club.get_socios_pagina(0, 5, filtro).map(dnis), Ok((vec![1, 2, 4], None)));
+        let filtro = FiltroSocios { actividad: Some(TENIS), ..Default::default() };
+        assert_eq!(club.get_socios_pagina(0, 5, filtro).map(dnis), Ok((vec![0, 3], None)));
+        let filtro = FiltroSocios { estado: Some(EstadoSocio::Suspendido), ..Default::default() };
+        assert_eq!(club.get_socios_pagina(0, 5, filtro).map(dnis), Ok((vec![4], None)));
+
+        club.realizar_pago(1, 2000).unwrap();
+        let cantidad = |(pagos, siguiente): (Vec<Pago>, Option<u64>)| (pagos.len(), siguiente);
+        assert_eq!(club.get_pagos_pagina(0, 4, FiltroPagos::default()).map(cantidad), Ok((4, Some(4))));
+        let filtro = FiltroPagos { pagado: Some(true), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 10, filtro).map(cantidad), Ok((1, None)));
+        let filtro = FiltroPagos { pagado: Some(false), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 10, filtro).map(cantidad), Ok((5, None)));
+        let filtro = FiltroPagos { categoria: Some(CATEGORIA_B), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 10, filtro).map(cantidad), Ok((1, None)));
+        // Filtrando por socio, se recorren sólo sus pagos
+        let filtro = FiltroPagos { socio: Some(1), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 1, filtro.clone()).map(cantidad), Ok((1, Some(1))));
+        assert_eq!(club.get_pagos_pagina(1, 1, filtro).map(cantidad), Ok((1, None)));
+        let filtro = FiltroPagos { socio: Some(9), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 1, filtro), Err(ClubError::SocioInexistente));
+        // Rangos de fechas
+        let filtro = FiltroPagos { vencimiento_desde: Some(Fecha::new(12, 1, 1970).unwrap()), ..Default::default() };
+        let (pagos, _) = club.get_pagos_pagina(0, 10, filtro).unwrap();
+        assert_eq!(pagos.len(), 1);
+        assert_eq!(pagos[0].get_vencimiento(), Fecha::new(10, 2, 1970).unwrap());
+        let hoy = club.obtener_fecha_actual();
+        let filtro = FiltroPagos { pagado_desde: Some(hoy), pagado_hasta: Some(hoy), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 10, filtro).map(cantidad), Ok((1, None)));
+        let filtro = FiltroPagos { vencimiento_hasta: Some(hoy), ..Default::default() };
+        assert_eq!(club.get_pagos_pagina(0, 10, filtro).map(cantidad), Ok((0, None)));
+    }
+
+    #[ink::test]
+    fn importar_socios_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        assert_eq!(club.importar_socios(vec![]), Err(ClubError::ValorInvalido));
+        let demasiados = (0..=MAXIMO_SOCIOS_POR_IMPORTACION as u128)
+            .map(|dni| SocioAImportar { dni: dni + 100, categoria: CATEGORIA_C, ..Default::default() })
+            .collect();
+        assert_eq!(club.importar_socios(demasiados), Err(ClubError::ValorInvalido));
+        assert_eq!(club.get_socios().unwrap().len(), 1);
+
+        let hoy = club.obtener_fecha_actual();
+        let alta = Fecha::new(1, 6, 1969).unwrap();
+        let mut mañana = hoy;
+        mañana.sumar_dias(1);
+        let fila = |dni: u128, categoria: IdCategoria| SocioAImportar { dni, nombre: "Importado".into(), categoria, ..Default::default() };
+        let resultados = club.importar_socios(vec![
+            SocioAImportar { alta: Some(alta), deuda: Some(1500), ..fila(1, CATEGORIA_C) },
+            fila(0, CATEGORIA_C),
+            fila(2, 9),
+            fila(3, CATEGORIA_B),
+            SocioAImportar { alta: Some(mañana), ..fila(4, CATEGORIA_C) },
+            SocioAImportar { actividades_elegidas: vec![TENIS], ..fila(5, CATEGORIA_B) },
+            fila(5, CATEGORIA_C),
+        ]).unwrap();
+        assert_eq!(resultados, vec![
+            Ok(()),
+            Err(ClubError::SocioYaRegistrado),
+            Err(ClubError::CategoriaInexistente),
+            Err(ClubError::CategoriaInvalida),
+            Err(ClubError::ValorInvalido),
+            Ok(()),
+            Err(ClubError::SocioYaRegistrado),
+        ]);
+
+        // Sólo se registran las filas válidas, con su fecha de alta y su deuda en el primer pago
+        let socios = club.get_socios().unwrap();
+        assert_eq!(socios.iter().map(|socio| socio.get_dni()).collect::<Vec<_>>(), vec![0, 1, 5]);
+        assert_eq!(socios[1].get_fecha_alta(), alta);
+        assert_eq!(socios[2].get_fecha_alta(), hoy);
+        assert_eq!(socios[2].get_categoria(), CATEGORIA_B);
+        assert_eq!(club.get_monto_a_pagar(1), Ok(3500));
+        assert_eq!(club.get_monto_a_pagar(5), Ok(3000));
+        assert_eq!(club.get_pagos(Some(4)), Err(ClubError::SocioInexistente));
+    }
+
+    #[ink::test]
+    fn grupo_familiar_test() {
+        let mut club = generar_club();
+        club.set_porcentaje_descuento_familiar(10).unwrap();
+        assert_eq!(club.set_porcentaje_descuento_familiar(100), Err(ClubError::ValorInvalido));
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(1, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+
+        // El pendiente del dependiente pasa al titular
+        club.agregar_dependiente(0, 1).unwrap();
+        assert_eq!(club.agregar_dependiente(0, 0), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.agregar_dependiente(1, 2), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.agregar_dependiente(2, 0), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.agregar_dependiente(2, 1), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.get_socio(0).unwrap().get_dependientes(), &[1]);
+        assert_eq!(club.get_socio(1).unwrap().get_titular(), Some(0));
+        assert!(club.get_pagos(Some(1)).unwrap()[0].es_cancelado());
+        assert_eq!(club.get_monto_a_pagar(0), Ok(4000));
+        assert_eq!(club.get_monto_a_pagar(1), Ok(4000));
+
+        // Se puede pagar con el dni de cualquiera del grupo, y el siguiente pago tiene el descuento familiar
+        club.realizar_pago(1, 4000).unwrap();
+        let pagos = club.get_pagos(Some(0)).unwrap();
+        assert!(pagos[0].es_pagado());
+        assert_eq!(pagos[1].get_monto(), 3600);
+        assert_eq!(club.get_pagos(Some(1)).unwrap().len(), 1);
+
+        club.agregar_dependiente(0, 2).unwrap();
+        assert_eq!(club.get_monto_a_pagar(0), Ok(3600 + 3000));
+        club.realizar_pago(0, 6600).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[2].get_monto(), 6300);
+
+        // Los dependientes suspendidos no se cobran
+        club.suspender_socio(2).unwrap();
+        assert_eq!(club.realizar_pago(2, 6300), Err(ClubError::SocioNoActivo));
+        club.realizar_pago(0, 6300).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[3].get_monto(), 3600);
+        // Ni se pueden quitar del grupo, porque se les generaría un pago propio
+        assert_eq!(club.quitar_dependiente(2), Err(ClubError::SocioNoActivo));
+        assert_eq!(club.get_socio(2).unwrap().get_titular(), Some(0));
+        club.reactivar_socio(2).unwrap();
+
+        // El titular no se puede dar de baja mientras tenga dependientes
+        assert_eq!(club.dar_de_baja_socio(0), Err(ClubError::GrupoFamiliarInvalido));
+        club.quitar_dependiente(2).unwrap();
+        assert_eq!(club.quitar_dependiente(2), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.get_socio(2).unwrap().get_titular(), None);
+        assert_eq!(club.get_monto_a_pagar(2), Ok(3000));
+
+        // Mientras el pendiente del dependiente tenga pagos parciales o un cupón no se puede agregar
+        club.registrar_nuevo_socio(3, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.realizar_pago_parcial(3, 100).unwrap();
+        assert_eq!(club.agregar_dependiente(0, 3), Err(ClubError::GrupoFamiliarInvalido));
+        let hoy = club.obtener_fecha_actual();
+        club.crear_cupon("FAMILIA".into(), Descuento::Fijo(500), hoy, hoy, 1, vec![CATEGORIA_C]).unwrap();
+        club.registrar_nuevo_socio_con_cupon(4, "".into(), CATEGORIA_C, vec![], "FAMILIA".into()).unwrap();
+        assert_eq!(club.agregar_dependiente(0, 4), Err(ClubError::GrupoFamiliarInvalido));
+        assert_eq!(club.get_pagos(Some(3)).unwrap()[0].get_monto_pagado(), 100);
+        assert!(!club.get_pagos(Some(4)).unwrap()[0].es_cancelado());
+
+        club.dar_de_baja_socio(1).unwrap();
+        assert_eq!(club.get_socio(0).unwrap().get_dependientes(), &[] as &[u64]);
+        club.dar_de_baja_socio(0).unwrap();
+    }
+
+    #[ink::test]
+    fn cupones_test() {
+        let mut club = generar_club();
+        let hoy = club.obtener_fecha_actual();
+        let mut en_10_dias = hoy;
+        en_10_dias.sumar_dias(10);
+        club.crear_cupon("VERANO".into(), Descuento::Porcentaje(20), hoy, en_10_dias, 2, vec![CATEGORIA_B]).unwrap();
+        assert_eq!(club.crear_cupon("VERANO".into(), Descuento::Fijo(100), hoy, en_10_dias, 1, vec![]), Err(ClubError::NombreRepetido));
+        assert_eq!(club.crear_cupon("X".into(), Descuento::Porcentaje(0), hoy, en_10_dias, 1, vec![]), Err(ClubError::ValorInvalido));
+        assert_eq!(club.crear_cupon("X".into(), Descuento::Porcentaje(101), hoy, en_10_dias, 1, vec![]), Err(ClubError::ValorInvalido));
+        assert_eq!(club.crear_cupon("X".into(), Descuento::Fijo(100), en_10_dias, hoy, 1, vec![]), Err(ClubError::ValorInvalido));
+        assert_eq!(club.crear_cupon("X".into(), Descuento::Fijo(100), hoy, en_10_dias, 0, vec![]), Err(ClubError::ValorInvalido));
+        assert_eq!(club.crear_cupon("X".into(), Descuento::Fijo(100), hoy, en_10_dias, 1, vec![3]), Err(ClubError::CategoriaInexistente));
+        assert_eq!(club.get_cupon("X".into()), Err(ClubError::CuponInexistente));
+
+        // Al registrarse
+        club.registrar_nuevo_socio_con_cupon(0, "".into(), CATEGORIA_B, vec![TENIS], "VERANO".into()).unwrap();
+        let pago = &club.get_pagos(Some(0)).unwrap()[0];
+        assert_eq!(pago.get_monto(), 2400);
+        assert_eq!(pago.get_cupon(), Some("VERANO"));
+        assert_eq!(pago.get_descuento_cupon(), 600);
+        assert_eq!(club.get_cupon("VERANO".into()).unwrap().get_usos(), 1);
+        // Si el cupón no sirve no se registra el socio
+        assert_eq!(club.registrar_nuevo_socio_con_cupon(1, "".into(), CATEGORIA_C, vec![], "VERANO".into()), Err(ClubError::CuponNoAplicable));
+        assert_eq!(club.registrar_nuevo_socio_con_cupon(1, "".into(), CATEGORIA_C, vec![], "X".into()), Err(ClubError::CuponInexistente));
+        assert_eq!(club.get_socios().unwrap().len(), 1);
+
+        // En un pago, sólo uno por pago y hasta que se acaben los usos
+        assert_eq!(club.aplicar_cupon(0, "VERANO".into()), Err(ClubError::CuponNoAplicable));
+        club.realizar_pago(0, 2400).unwrap();
+        club.aplicar_cupon(0, "VERANO".into()).unwrap();
+        let pago = &club.get_pagos(Some(0)).unwrap()[1];
+        assert_eq!(pago.get_monto(), 2400);
+        assert_eq!(pago.get_cupon(), Some("VERANO"));
+        club.registrar_nuevo_socio(2, "".into(), CATEGORIA_B, vec![TENIS]).unwrap();
+        assert_eq!(club.aplicar_cupon(2, "VERANO".into()), Err(ClubError::CuponNoAplicable));
+        // El siguiente pago ya no tiene el cupón
+        club.realizar_pago(0, 2400).unwrap();
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[2].get_cupon(), None);
+        assert_eq!(club.get_pagos(Some(0)).unwrap()[2].get_monto(), 3000);
+
+        // Sólo dentro de su vigencia
+        let mut en_5_dias = hoy;
+        en_5_dias.sumar_dias(5);
+        club.crear_cupon("FIJO".into(), Descuento::Fijo(500), en_5_dias, en_10_dias, 10, vec![]).unwrap();
+        assert_eq!(club.aplicar_cupon(2, "FIJO".into()), Err(ClubError::CuponNoAplicable));
+        avanzar_dias(5);
+        club.aplicar_cupon(2, "FIJO".into()).unwrap();
+        assert_eq!(club.get_monto_a_pagar(2), Ok(2500));
+        avanzar_dias(6);
+        assert_eq!(club.aplicar_cupon(0, "FIJO".into()), Err(ClubError::CuponNoAplicable));
+    }
+
+    #[ink::test]
+    fn baja_con_cuenta_vinculada_test() {
+        let mut club = generar_club();
+        club.registrar_nuevo_socio(0, "".into(), CATEGORIA_C, vec![]).unwrap();
+        club.vincular_cuenta(0, bob()).unwrap();
+        club.dar_de_baja_socio(0).unwrap();
+        ser_bob();
+        assert_eq!(club.mi_socio().unwrap().get_estado(), EstadoSocio::Baja);
+        assert_eq!(club.mi_pago_pendiente(), Err(ClubError::SocioNoActivo));
+        assert_eq!(club.pagar_mi_cuota(), Err(ClubError::SocioNoActivo));
+    }
+
+    #[ink::test]
+    fn valores_invalidos_test() {
+        let mut club = generar_club();
+        assert_eq!(club.set_cantidad_pagos_bonificacion(0), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_porcentaje_bonificacion_pagos_consecutivos(0), Err(ClubError::ValorInvalido));
+        assert_eq!(club.set_porcentaje_bonificacion_pagos_consecutivos(100), Err(ClubError::ValorInvalido));
+        // Los valores no cambiaron
+        assert_eq!(club.get_cantidad_pagos_bonificacion(), 5);
+        assert_eq!(club.get_porcentaje_bonificacion_pagos_consecutivos(), 10);
     }
     
 }